@@ -50,16 +50,69 @@ fn args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .takes_value(true)
             .multiple(true)
             .use_delimiter(true)
-            // screaming-snake, snake, kebab, camel, pascal, hungarian-notation
-            // S, s, k, c, p, h
-            .possible_values(&["S", "s", "k", "c", "p", "h"])
+            // screaming-snake, snake, kebab, camel, pascal, hungarian-notation,
+            // train, cobol, dot, path, flat, upper-flat, ada, title, sentence
+            // S, s, k, c, p, h, T, K, d, r, f, F, A, t, e,
+            // and negated ones like "!k"
+            .possible_values(&[
+                "S", "s", "k", "c", "p", "h", "T", "K", "d", "r", "f", "F", "A",
+                "t", "e", "!S", "!s", "!k", "!c", "!p", "!h", "!T", "!K", "!d",
+                "!r", "!f", "!F", "!A", "!t", "!e",
+            ])
             .hide_possible_values(true),
+        Arg::with_name("exclude-format")
+            .long("exclude-format")
+            .help("Set which formats will be discarded, same as negated `--filter` values")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true)
+            .possible_values(&[
+                "S", "s", "k", "c", "p", "h", "T", "K", "d", "r", "f", "F", "A",
+                "t", "e",
+            ])
+            .hide_possible_values(true),
+        Arg::with_name("hungarian-prefixes")
+            .long("hungarian-prefixes")
+            .long_help(HUNGARIAN_PREFIXES_HELP)
+            .help(
+                "Set the prefixes of hungarian notation; \
+                default: i,str,b,p,lp,sz,m_,g_,s_",
+            )
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true),
         Arg::with_name("eof")
             .short("e")
             .long("eof")
             .long_help(EOF_HELP)
             .help("Set logical EOF string; If occurs, the rest of the input is ignored")
             .takes_value(true),
+        Arg::with_name("sof")
+            .long("sof")
+            .long_help(SOF_HELP)
+            .help("Set logical SOF string; the input before it is ignored")
+            .takes_value(true),
+        Arg::with_name("region")
+            .long("region")
+            .long_help(REGION_HELP)
+            .help(
+                "Only match the input between begin and end markers, \
+                the separator between them is \"{}\"",
+            )
+            .takes_value(true)
+            .multiple(true)
+            .number_of_values(1),
+        Arg::with_name("regex-markers")
+            .long("regex-markers")
+            .help("Treat `--sof`, `--eof` and `--region` markers as regular expressions"),
+        Arg::with_name("encoding")
+            .long("encoding")
+            .long_help(ENCODING_HELP)
+            .help("Set the encoding of input without BOM; default: utf-8")
+            .takes_value(true),
+        Arg::with_name("lossy")
+            .long("lossy")
+            .help("Replace malformed bytes in input instead of exiting with an error"),
         Arg::with_name("locator")
             .short("l")
             .long("locator")
@@ -72,6 +125,53 @@ fn args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .takes_value(true)
             .multiple(true)
             .use_delimiter(true),
+        Arg::with_name("word-pattern")
+            .long("word-pattern")
+            .long_help(WORD_PATTERN_HELP)
+            .help(
+                "Set the regex or built-in name (default, ident, dotted, \
+                sigil, phrase) of identifiers between locator pairs",
+            )
+            .takes_value(true),
+        Arg::with_name("keep-order")
+            .long("keep-order")
+            .help(
+                "Keep the order of matches same as the order \
+                of their first occurrences in input",
+            ),
+        Arg::with_name("no-pragmas")
+            .long("no-pragmas")
+            .long_help(NO_PRAGMAS_HELP)
+            .help("Don't honour inline directives like \"naming:ignore-next-line\""),
+        Arg::with_name("lang")
+            .long("lang")
+            .long_help(LANG_HELP)
+            .help(
+                "Use built-in locators of given language, \
+                or auto (detected from file extension)",
+            )
+            .takes_value(true)
+            .possible_values(&[
+                "auto", "rust", "java", "python", "go", "js", "c",
+            ])
+            .conflicts_with("locator"),
+        Arg::with_name("lexer")
+            .long("lexer")
+            .long_help(LEXER_HELP)
+            .help(
+                "Skip words in comments and string literals with a lexer \
+                of given syntax; without locators, capture identifier tokens",
+            )
+            .takes_value(true)
+            .possible_values(&["auto", "c", "js", "python", "shell"]),
+        Arg::with_name("include-comments")
+            .long("include-comments")
+            .help("Let `--lexer` keep the words inside comments")
+            .requires("lexer"),
+        Arg::with_name("include-strings")
+            .long("include-strings")
+            .help("Let `--lexer` keep the words inside string literals")
+            .requires("lexer"),
         Arg::with_name("output")
             .short("o")
             .long("output")
@@ -82,18 +182,147 @@ fn args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .use_delimiter(true)
             // can't output hungarian notation format
             // so there is no "h" value
-            .possible_values(&["S", "s", "k", "c", "p"])
+            .possible_values(&[
+                "S", "s", "k", "c", "p", "T", "K", "d", "r", "f", "F", "A", "t", "e",
+            ])
+            .hide_possible_values(true),
+        Arg::with_name("acronyms")
+            .long("acronyms")
+            .long_help(ACRONYMS_HELP)
+            .help("Keep given words in uppercase in camelCase and PascalCase output")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true),
+        Arg::with_name("digits")
+            .long("digits")
+            .long_help(DIGITS_HELP)
+            .help(
+                "Set which word digits belong to: \
+                attach-previous, separate or attach-next; default: attach-previous",
+            )
+            .takes_value(true)
+            .possible_values(&["attach-previous", "separate", "attach-next"])
             .hide_possible_values(true),
+        Arg::with_name("strip-affixes")
+            .long("strip-affixes")
+            .long_help(STRIP_AFFIXES_HELP)
+            .help("Don't keep leading/trailing underscores and sigils in converted matches"),
         Arg::with_name("json")
             .long("json")
             .long_help(JSON_HELP)
             .help("Output in json format"),
+        Arg::with_name("positions")
+            .long("positions")
+            .long_help(POSITIONS_HELP)
+            .help("Output the positions of all occurrences of each match")
+            .conflicts_with("regex"),
+        Arg::with_name("keywords")
+            .long("keywords")
+            .long_help(KEYWORDS_HELP)
+            .help(
+                "Drop keywords of given language, or auto, all, none; \
+                default: the language of --lang",
+            )
+            .takes_value(true)
+            .possible_values(&[
+                "auto", "all", "none", "rust", "java", "python", "go", "js", "c",
+            ])
+            .hide_possible_values(true),
+        Arg::with_name("exclude-words")
+            .long("exclude-words")
+            .long_help(EXCLUDE_WORDS_HELP)
+            .help("Drop the words listed in given file from matches")
+            .takes_value(true)
+            .value_name("FILE")
+            .multiple(true)
+            .number_of_values(1),
+        Arg::with_name("match")
+            .long("match")
+            .long_help(MATCH_HELP)
+            .help("Only keep matches that match one of given regexes, like \"Id$\"")
+            .takes_value(true)
+            .value_name("REGEX")
+            .multiple(true)
+            .number_of_values(1),
+        Arg::with_name("exclude-match")
+            .long("exclude-match")
+            .help("Drop matches that match one of given regexes, like \"^test_\"")
+            .takes_value(true)
+            .value_name("REGEX")
+            .multiple(true)
+            .number_of_values(1),
+        Arg::with_name("min-length")
+            .long("min-length")
+            .help("Drop matches with fewer characters than given count")
+            .takes_value(true)
+            .value_name("N"),
+        Arg::with_name("max-length")
+            .long("max-length")
+            .help("Drop matches with more characters than given count")
+            .takes_value(true)
+            .value_name("N"),
+        Arg::with_name("min-words")
+            .long("min-words")
+            .help("Drop matches with fewer words than given count, like \"size\" in 2")
+            .takes_value(true)
+            .value_name("N"),
+        Arg::with_name("max-words")
+            .long("max-words")
+            .help("Drop matches with more words than given count")
+            .takes_value(true)
+            .value_name("N"),
+        Arg::with_name("skip-numbers")
+            .long("skip-numbers")
+            .help("Drop numeric literals like \"42\", \"1_000\" and \"0xFF\""),
+        Arg::with_name("dedup")
+            .long("dedup")
+            .long_help(DEDUP_HELP)
+            .help(
+                "Set how matches are deduplicated: \
+                exact, ignore-case or words; default: exact",
+            )
+            .takes_value(true)
+            .possible_values(&["exact", "ignore-case", "words"])
+            .hide_possible_values(true),
+        Arg::with_name("stats")
+            .long("stats")
+            .long_help(STATS_HELP)
+            .help("Output how many times and in how many files each match occurs"),
+        Arg::with_name("sort-by-frequency")
+            .long("sort-by-frequency")
+            .help(
+                "Sort matches by how many times (then in how many files) \
+                they occur, most frequent first",
+            ),
         Arg::with_name("regex")
             .long("regex")
             .long_help(REGEX_HELP)
             .help("Output as an OR-joined (e.g. \"x|y|z\") regular expression"),
+        Arg::with_name("keep-going")
+            .long("keep-going")
+            .long_help(KEEP_GOING_HELP)
+            .help("Skip input files that can't be read, instead of exiting at the first one"),
+        Arg::with_name("include")
+            .long("include")
+            .long_help(INCLUDE_HELP)
+            .help("Only read files matching given globs in directories")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true),
+        Arg::with_name("exclude")
+            .long("exclude")
+            .help("Skip files and directories matching given globs in directories")
+            .takes_value(true)
+            .multiple(true)
+            .use_delimiter(true),
+        Arg::with_name("follow-links")
+            .long("follow-links")
+            .help("Follow symbolic links when walking directories"),
         Arg::with_name("files")
-            .help("pass file names, or directly pass text via shell pipe")
+            .help(
+                "pass file or directory names, \
+                or directly pass text via shell pipe",
+            )
             .multiple(true),
     ]
         .into_boxed_slice()
@@ -105,36 +334,150 @@ discard other format matches. Default add all formats into
 filter, which means do not discard any match that can be
 matched by one of the formats.
 
-There are 6 available formats:
+There are 15 available formats:
     * S -> SCREAMING_SNAKE_CASE
     * s -> snake_case
     * k -> kebab-case
     * c -> camelCase
-    * P -> PascalCase
+    * p -> PascalCase
+    * T -> Train-Case
+    * K -> COBOL-CASE
+    * d -> dot.case
+    * r -> path/case
+    * f -> flatcase
+    * F -> UPPERFLATCASE
+    * A -> Ada_Case
+    * t -> Title Case
+    * e -> Sentence case
     * h -> hungarian notation
 
+NOTE: A single word like \"name\" is in several formats at the same
+time (snake_case, kebab-case, camelCase, flatcase...). And the default
+`--word-pattern` doesn't match dots, slashes and spaces, pass a pattern
+like \"dotted\" to match dot.case words, or \"phrase\" to match
+space-separated labels like \"Page Size\" and \"Page size\".
+
 The last format, hungarian notation means that let the tool
-recognize camel case matches as hungarian notation style,
-strip the first lowercase word (as this identifier's type prefix)
-and keep the remain part for further converting.
-example: \"iPageSize\" --strip\"i\"--> \"PageSize\" --> ...
+recognize matches starting with a known prefix (see
+`--hungarian-prefixes` option) as hungarian notation style,
+strip the prefix and keep the remain part for further converting.
+example: \"strPageSize\" --strip\"str\"--> \"PageSize\" --> ...
+Matches without a known prefix, like \"userName\", are kept as they
+are if their formats are in the filter, so hungarian notation (h)
+can be combined with camel case (c). In json format, the stripped
+prefix is output as an extra field: \"prefix\":\"str\".
+
+A format prefixed with \"!\" (or passed to `--exclude-format` option)
+is discarded. When only negated formats (and hungarian notation)
+are passed, they are discarded from all formats. For example:
+
+    $ naming --filter='!k' src/            # all formats except kebab-case
+    $ naming --filter='h,!S' src/          # S is dropped from \"S,s,k,c,p,h\"
+    $ naming --exclude-format=k,c src/     # same as --filter='!k,!c'
+
+Excluding all formats will let the tool exits with non-zero signal.
+
+Besides formats, matches can be filtered by their lengths (counted
+in characters) and word counts (split by separators and humps of
+camel case) with `--min-length`, `--max-length`, `--min-words` and
+`--max-words` options, and numeric literals can be dropped with
+`--skip-numbers` flag. For example, drop one-letter loop variables
+and single-word names:
+
+    $ naming --min-length=2 --min-words=2 src/*.c
+\n\n";
+
+const HUNGARIAN_PREFIXES_HELP: &str = "\
+Set the prefixes that hungarian notation (h) in `--filter` option
+recognizes, they replace the default ones:
+
+    i, str, b, p, lp, sz    -> type prefixes, like \"iCount\" and \"szName\"
+    m_, g_, s_              -> scope prefixes, like \"m_count\"
 
-[!]NOTE: Due to the implementation of hungarian notation's matching,
-at most one of the two, hungarian notation (h) and camel case (c)
-can appear in `--filter` option. Pass both of them will let the tool
-exits with non-zero signal.
+A scope prefix (ends with \"_\") can be followed by type prefixes,
+and type prefixes can be chained, like \"m_lpszName\". Type prefixes
+must be followed by an uppercase letter, so \"is_valid\" and \"string\"
+are not recognized. For example:
+
+    $ naming --filter=h,c --hungarian-prefixes=n,str,m_ src/*.cpp
+\n\n";
+
+const MATCH_HELP: &str = "\
+Only keep the matches that match one of given regular expressions,
+and drop the ones that match one of `--exclude-match` option's
+regular expressions, with the same syntax as `--locator` option.
+Expressions search anywhere in the match unless they are anchored
+with \"^\" or \"$\". Pass these options multiple times for
+multiple expressions. For example, scope a rename to the ID fields
+except the ones in tests:
+
+    $ naming --match=\"Id$\" --exclude-match=\"^test\" --output=s src/
+
+NOTE:
+Expressions are applied on the origin matches, before they are
+converted to other formats, and before `--dedup` groups them.
 \n\n";
 
 const EOF_HELP: &str = "\
 Set logical EOF string, if occurs, the rest of the input is ignored.
-This will actually be done *after reading the whole content*, so
-it is recommended to use the following command to actually intercept
-large files (to prevent long processing time):
+Input is read and matched piece by piece, and the reading stops as
+soon as the EOF string occurs, so the rest of a large file (or stdin)
+is never read. For example:
+
+    $ naming --eof=\"__END__\" large_file.pl
+
+The EOF string is applied on each input file separately.
+\n\n";
+
+const SOF_HELP: &str = "\
+Set logical SOF (start of file) string, the input before the end of
+its first occurrence is ignored, and if it doesn't occur, the whole
+input is ignored. Same as `--eof`, it's applied on each input
+file separately, and the EOF string is searched after it.
+
+Pass `--regex-markers` flag to use regular expressions as the markers
+of this option, `--eof` and `--region` options, for example:
+
+    $ naming --regex-markers --sof=\"(?m)^__DATA__$\" script.pl
+
+NOTE:
+A regex occurrence may depend on the text around it, so a regex marker
+is only taken after 64 KiB of input from its start is read, and an
+occurrence longer than that may be cut short. The reading still stops at a regex EOF.
+\n\n";
+
+const REGION_HELP: &str = "\
+Only match identifiers in regions between a begin marker and the next
+end marker, the delimiter between them is a pair of brackets \"{}\",
+like \"// naming:begin{}// naming:end\". A region without an end marker
+lasts to the end of the input. Pass this option multiple times for
+multiple kinds of regions, the input outside all of them is ignored.
+For example, only match the identifiers in generated sections:
+
+    $ naming --region=\"// <generated>{}// </generated>\" src/*.java
+
+NOTE:
+Ignored input is not removed but blanked out, so the line and column
+numbers of `--positions` flag are still those in the origin input.
+\n\n";
 
-    $ sed '/<eof-word>/q' file_name | naming
+const ENCODING_HELP: &str = "\
+Set the encoding of input files (and stdin) that don't start with a BOM,
+like \"latin1\", \"gbk\", \"shift_jis\" or \"utf-16le\", check all labels in:
 
-Command above means that output content from first line
-to the line contains given eof-word, to this tool.
+    https://encoding.spec.whatwg.org/#names-and-labels
+
+Default(utf-8). Files starting with a UTF-8 or UTF-16 BOM are always
+decoded in the encoding that the BOM indicates.
+
+Input that contains malformed bytes of the encoding will let the tool
+exits with non-zero signal, pass `--lossy` flag to replace them with
+the replacement character U+FFFD and go on.
+
+NOTE:
+Files with NUL bytes in their first 8KB are treated as binary files,
+they are skipped with a warning in stderr, unless they are decoded
+as UTF-16 (with a BOM or this option).
 \n\n";
 
 const LOCATOR_HELP: &str = "\
@@ -151,44 +494,163 @@ Default(1): \"(?<=\\b){}(?=\\b)\", which will match the valid identifiers
 separated by \"Unicode word boundary (\\w on one side and \\W, \\A, or \\z
 on the other side)\" positions.
 
+A locator can contain several placeholders to capture several
+identifiers in one match, and a placeholder can be named like
+\"{name}\", the name is output as a tag of the match in json format.
+For example, this locator captures both field names and types:
+
+    $ echo \"size: usize\" | naming --json -l=\"{name}:\\s*{type}\"
+
 NOTE:
-Any incomplete pair value that couldn't be split
-into two part by space delimiter like \"a\",\"{}a\",\"a{}\"
-will let the tool outputs nothing and exits with non-zero signal.
+Any value that doesn't contain a placeholder like \"a\",
+or contains capture groups like \"(a|b){}\" (use a non-capturing
+group \"(?:a|b)\" instead), will let the tool outputs nothing
+and exits with non-zero signal.
 
 NOTE:
-Due to the technical limitation, there is no guarantee that
-the order of matches in output (in normal output format, that
-means the order of lines) will be same as the order in
-origin file content.
+By default, each generated pattern will matches the input
+independently and all matches will be concatenated according
+to the order of values passed to this `--locator` option,
+so the order of matches in output (in normal output format,
+that means the order of lines) may not be same as the order
+in origin file content.
+
+Pass `--keep-order` flag to merge the matches of all patterns
+by their positions, as if all patterns were matched in a single
+left-to-right scan, then the output order will be same as
+the order of first occurrences in input. For example:
+
+    $ echo \"@first@ #second#\" | naming --keep-order -l=\"#{}#,@{}@\"
+
+Commands above output \"first\" before \"second\".
+
+\n\n";
+
+const WORD_PATTERN_HELP: &str = "\
+Set the regular pattern that matches the identifier itself,
+which is inserted in place of each placeholder \"{}\" of locators
+(including the built-in locators of `--lang` option).
+
+There are 5 built-in patterns that can be passed by name:
+    * default -> identifier characters and hyphens, like \"page_size\",
+                 \"page-size\" and \"变量\"
+    * ident   -> identifiers of most programming languages, which start
+                 with a letter or underscore and don't contain hyphens
+    * dotted  -> same as default, but words can be joined by dots,
+                 like keys \"log.level\" in configuration files
+    * sigil   -> identifiers with optional sigils and predicate suffixes,
+                 like \"$scope\", \"@ivar\" and \"empty?\" in ruby, perl, php
+    * phrase  -> words separated by single spaces, like labels \"Page size\"
+                 and headings \"Page Size\", use it with a locator like
+                 \"label='{}'\", or the default locator matches whole
+                 lines of words
+
+Other values are treated as a regex, with the same syntax as `--locator`.
+
+NOTE:
+The pattern must not contain capture groups, use non-capturing groups
+like \"(?:...)\" instead, or the tool exits with non-zero signal.
+
+NOTE:
+The default locator matches at \"\\b\" positions, which don't stand
+beside sigils, pass a locator like \"(?<![\\w$@]){}(?![\\w?!])\"
+when using the sigil pattern.
+\n\n";
+
+const NO_PRAGMAS_HELP: &str = "\
+By default, these inline directives (usually written in comments,
+and only in comments when `--lexer` is on) in input are honoured:
+
+    naming:ignore-next-line         ignore the line after it;
+    naming:ignore-start             ignore the lines between them and
+    naming:ignore-end               their own lines, or to the end of
+                                    input if unclosed;
+    naming:ignore-word foo, fooBar  ignore listed words in the whole input.
+
+The words of directives themselves are never captured.
+For example, to skip vendored code and intentional FFI names:
+
+    // naming:ignore-word glGenBuffers
+    // naming:ignore-start
+    ...vendored code...
+    // naming:ignore-end
 
-Each generated pattern will matches the input independently
-and all matches will be concatenated according to the order of
-values passed to this `--locator` option.
+Pass this flag to ignore such directives and match the input as it is.
+\n\n";
+
+const LANG_HELP: &str = "\
+Use the built-in locators of given language instead of `--locator`,
+which capture the identifiers appear in declarations, assignments,
+parameters and fields of that language.
 
-If you want to keep this order, you can use tools like `grep`
-with a complex regex that contains lookaround syntax and OR operator
-to match all patterns at once, then extract the wanted part.
-For example:
+There are 6 available languages:
+    * rust
+    * java
+    * python
+    * go
+    * js     (also used for typescript files)
+    * c      (also used for c++ files)
 
-    $ echo \"@first@ #second#\" | \\
-     grep -oP \"(?<=@)[a-z]+(?=@)|(?<=#)[a-z]+(?=#)\" | naming
+Pass \"auto\" to detect each input file's language from its extension
+(like \"main.rs\" -> rust), files with unknown extensions and stdin are
+matched with the default locator of `--locator` option. Without this
+option, all input is matched with the default locator.
 
-Commands above piped \"first\\nsecond\" to this tool.
+NOTE:
+The presets are regular expressions rather than language parsers,
+they may miss or wrongly capture words in complex statements.
+\n\n";
 
+const LEXER_HELP: &str = "\
+Tokenize input with a lexer of given syntax, and skip the words inside
+comments and string literals, which are usually not identifiers.
+
+There are 4 available syntaxes:
+    * c      -> `//` and `/* */` comments, `\"...\"`, `'c'` and `...` literals,
+                for c, c++, java, go, rust...
+    * js     -> `//` and `/* */` comments, `'...'`, `\"...\"` and `...`
+                literals, for javascript and typescript
+    * python -> `#` comments, `'...'`, `\"...\"` and triple-quoted literals,
+                for python
+    * shell  -> `#` comments, `'...'` and `\"...\"` literals,
+                for shell scripts and configuration files.
+Or pass `auto` to detect the syntax from each file's extension,
+`c` is used for stdin and unknown extensions.
+
+When neither `--locator` nor `--lang` option is passed, all
+identifier tokens in code are captured (numbers, punctuations and
+keywords of each file's language are skipped, the keywords of c, js
+and python are used when a file's language is unknown). Otherwise,
+locators are matched on the input with comments and string literals
+blanked out, along with the names in statements that only refer to
+existing names, like \"return a - b;\" and Java's \"package data;\".
+The language of `--lang` option is used instead of each file's
+language if it's passed.
+
+Pass `--include-comments` and `--include-strings` flags to
+keep the words inside comments and string literals.
 \n\n";
 
 const OUTPUT_HELP: &str = "\
 Set which naming cases that matches will be converted to.
 
-There are 5 available formats:
-    * S -> SCREAMING_SNAKE_CASE
-    * s -> snake_case
-    * k -> kebab-case
-    * c -> camelCase
-    * P -> PascalCase
-
-Default output all formats in a fix order --
+There are 14 available formats:
+    * S -> SCREAMING_SNAKE_CASE   (json key: screaming_snake)
+    * s -> snake_case             (json key: snake)
+    * k -> kebab-case             (json key: kebab)
+    * c -> camelCase              (json key: camel)
+    * p -> PascalCase             (json key: pascal)
+    * T -> Train-Case             (json key: train)
+    * K -> COBOL-CASE             (json key: cobol)
+    * d -> dot.case               (json key: dot)
+    * r -> path/case              (json key: path)
+    * f -> flatcase               (json key: flat)
+    * F -> UPPERFLATCASE          (json key: upper_flat)
+    * A -> Ada_Case               (json key: ada)
+    * t -> Title Case             (json key: title)
+    * e -> Sentence case          (json key: sentence)
+
+Default output the first 5 formats in a fix order --
 6 words separated by spaces, one line per match in output,
 origin match followed with naming cases of it:
 
@@ -203,6 +665,79 @@ this option, so you can arrange the order of formats
 in line) in output as you wish.
 This will be useful when you want to process the output
 of this tool, like, pass them to `xargs`.
+
+NOTE: Title Case and Sentence case contain spaces, which can't be
+told apart from the separators in normal output format, so they are
+double-quoted like \"Page Size\", which `xargs` takes as one word.
+
+Non-ASCII letters are converted with Unicode's default
+(locale-independent) case mappings, for example, \"größe\" is
+converted to \"GRÖSSE\", Turkish \"İ\" is lower-cased to \"i̇\"
+(with a combining dot above), and caseless letters like
+\"变量\" are kept as they are.
+\n\n";
+
+const ACRONYMS_HELP: &str = "\
+Set the acronyms that are kept in uppercase when matches are converted
+to camelCase and PascalCase, they are matched case-insensitively.
+The first word of camelCase is still in lowercase. For example:
+
+    $ echo user_id xml_http_request | naming --acronyms=ID,XML --output=c,p
+    user_id userID UserID
+    xml_http_request xmlHttpRequest XMLHttpRequest
+
+Without this option, acronyms are converted like other words, for
+example, \"userID\" is converted to \"userId\". Note that acronyms in
+matches are always recognized as words, like \"HTTPServer\" is split
+into \"HTTP\" and \"Server\", and converted to \"http_server\".
+A lowercase plural \"s\" stays with its acronym, like \"userIDs\" is
+split into \"user\" and \"IDs\", and converted to \"user_ids\" (and
+\"userIDs\" with `--acronyms=ID`).
+\n\n";
+
+const DIGITS_HELP: &str = "\
+Set which word a run of digits belongs to when matches are split
+into words, in both `--filter` (for `--min-words`, `--max-words` and
+`--dedup=words`) and all output formats.
+
+There are 3 available policies:
+    * attach-previous -> \"v2Api\" is split into \"v2\" and \"Api\"
+    * separate        -> \"v2Api\" is split into \"v\", \"2\" and \"Api\"
+    * attach-next     -> \"v2Api\" is split into \"v\" and \"2Api\"
+
+Default(attach-previous). Explicit separators are always kept, so
+\"PAGE_2\" is split into \"PAGE\" and \"2\" with every policy, but its
+camelCase \"page2\" is converted back to \"page2\" with attach-previous.
+Use the same policy in both directions to round-trip conversions:
+
+    $ echo utf8Decode | naming --digits=separate --output=s
+    utf8Decode utf_8_decode
+    $ echo utf_8_decode | naming --digits=separate --output=c
+    utf_8_decode utf8Decode
+\n\n";
+
+const STRIP_AFFIXES_HELP: &str = "\
+Leading underscores and sigils (\"_\", \"$\" and \"@\") and trailing
+underscores and predicate suffixes (\"?\" and \"!\") of matches are
+stripped before their formats are detected, so \"_privateName\",
+\"__init__\" and \"empty?\" are matched like \"privateName\", \"init\"
+and \"empty\". By default, these affixes are
+re-applied to every converted word:
+
+    $ echo _privateName | naming --output=s,S
+    _privateName _private_name _PRIVATE_NAME
+
+With this flag, converted words don't keep their affixes, but the
+origin match keeps them anyway:
+
+    $ echo _privateName | naming --output=s,S --strip-affixes
+    _privateName private_name PRIVATE_NAME
+
+In json format, affixes are output as an extra field:
+\"affix\":{\"leading\":\"_\",\"trailing\":\"\"}.
+
+NOTE: The default `--word-pattern` doesn't match sigils, pass the
+\"sigil\" pattern to match words like \"$scope\", \"@ivar\" and \"save!\".
 \n\n";
 
 const JSON_HELP: &str = "\
@@ -225,6 +760,145 @@ the real output doesn't contain spaces, looks like:
 
 NOTE: The presence of the format fields in result
 depends on whether it is present in `--output` option.
+
+NOTE: Matches captured by named placeholders of `--locator`
+option have an extra field, names of these placeholders:
+\"tags\":[\"name\",\"type\"].
+\n\n";
+
+const POSITIONS_HELP: &str = "\
+Output the positions of all occurrences of each match,
+so that you can jump to them with an editor, or feed them
+to other tools for precise edits.
+
+In normal output format, one line per occurrence, prefixed with
+its position like grep's output (there is no path for stdin):
+
+    <path>:<line>:<column>:<origin match 1> <SCREAMING_SNAKE_CASE> ...
+    <path>:<line>:<column>:<origin match 1> <SCREAMING_SNAKE_CASE> ...
+    <path>:<line>:<column>:<origin match 2> <SCREAMING_SNAKE_CASE> ...
+    ...
+
+Line and column (counted in characters) start from 1.
+
+Combine with `--json` option, each element in \"result\" array
+gets an extra field (beautified, path is null for stdin):
+
+    \"positions\":[
+        {\"path\":\"<path>\",\"line\":1,\"column\":1,\"offset\":0},
+        ...
+    ]
+
+where \"offset\" is the byte offset starts from 0.
+\n\n";
+
+const KEYWORDS_HELP: &str = "\
+Drop language keywords, primitive and common built-in type names
+(like \"return\", \"int\", \"self\" and \"String\") from matches,
+so that only the identifiers of your project are left.
+
+There are 4 kinds of available values:
+    * auto     -> use the list of the language detected from
+                  each file's extension, stdin is kept as it is
+    * all      -> use the lists of all languages on all input
+    * none     -> keep keywords
+    * <lang>   -> use the list of given language on all input,
+                  one of: rust, java, python, go, js, c
+                  (c++ keywords like \"class\" and \"new\" are only
+                  dropped in .cc, .cpp, .cxx and .hpp files)
+
+Default to the language of `--lang` option if it's passed (auto with
+\"--lang=auto\"), otherwise none, keywords are kept along with
+the words that `--locator` option matches.
+\n\n";
+
+const EXCLUDE_WORDS_HELP: &str = "\
+Drop the words listed in given file from matches, in all input.
+Words are separated by whitespaces, and the rest of a line
+after \"#\" is a comment. Words are matched case-sensitively,
+and are dropped before `--filter` option is applied.
+Pass this option multiple times to use multiple files, for example:
+
+    $ cat stopwords.txt
+    # wire protocol fields
+    msg_id msgType
+    $ naming --exclude-words=stopwords.txt src/
+\n\n";
+
+const DEDUP_HELP: &str = "\
+Set which matches are treated as the same identifier,
+and output only once.
+
+There are 3 available modes:
+    * exact       -> same strings, like \"pageSize\" and \"pageSize\"
+    * ignore-case -> same strings ignoring letter cases,
+                     like \"pageSize\" and \"PageSize\"
+    * words       -> same word sequences in different naming cases,
+                     like \"pageSize\", \"page_size\" and \"PAGE_SIZE\"
+
+Default(exact). With other modes, all observed spellings of an
+identifier are grouped under the first one. In normal output format
+(and with `--regex` option), the origin match becomes the spellings
+joined with commas:
+
+    $ echo \"pageSize page_size\" | naming --dedup=words --output=S
+    pageSize,page_size PAGE_SIZE
+
+In json format, the \"origin\" field is the first spelling,
+and there is an extra field: \"spellings\":[\"pageSize\",\"page_size\"].
+\n\n";
+
+const STATS_HELP: &str = "\
+Output how many times and in how many files each match occurs
+in input (stdin counts as one file), this is useful to find out
+which identifiers should be migrated first in a naming cleanup.
+
+In normal output format (and with `--regex` option),
+each line starts with the two numbers:
+
+    <occurrences> <files> <origin match> <SCREAMING_SNAKE_CASE> ...
+
+In json format, each element has two extra fields:
+
+    {\"origin\":\"...\",...,\"count\":<occurrences>,\"files\":<files>}
+
+Combine with `--sort-by-frequency` flag to list
+the most frequent identifiers first:
+
+    $ naming --stats --sort-by-frequency --output=s src/*.rs | head
+\n\n";
+
+const KEEP_GOING_HELP: &str = "\
+Skip the input files (and directory entries) that can't be read
+or decoded, and go on processing the rest files, instead of
+exiting at the first one of them.
+
+Errors of skipped files are reported in stderr, or with `--json`
+option, in an \"errors\" array field under the root:
+
+    {\"result\":[...],\"errors\":[\"naming: a.txt: ...\",...]}
+
+When some files are skipped, the tool exits with signal 2
+after output the results of the rest files, rather than 1,
+which means the tool failed and output nothing.
+\n\n";
+
+const INCLUDE_HELP: &str = "\
+Only read the files whose paths match one of given globs,
+when walking directories in `<files>` arguments.
+Globs are matched against paths relative to the walked directory,
+like \"*.rs\" or \"src/**/*.java\", and `--exclude` globs
+take precedence over this option's globs.
+
+Directories are walked recursively, except:
+    * files and directories ignored by `.gitignore` and `.ignore` files;
+    * hidden files and directories (names start with \".\");
+    * `target` and `node_modules` directories;
+    * symbolic links, unless `--follow-links` flag is passed.
+
+Files passed explicitly are always read. For example:
+
+    $ naming --include=\"*.rs\" --exclude=\"tests/**\" src/ build.rs
 \n\n";
 
 const REGEX_HELP: &str = "\
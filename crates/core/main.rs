@@ -7,15 +7,23 @@ use naming_clt_lib::*;
 
 mod app;
 
+/// Some input files can't be read in `--keep-going` mode,
+/// but the readable ones are processed.
+const PARTIAL_FAILURE_EXIT_CODE: i32 = 2;
+
 fn main() {
     match operate(app::app().get_matches()) {
-        Ok(output) => {
+        Ok((output, errors)) => {
             if is_atty_stdout() {
                 println!("{}", output);
             } else {
                 print!("{}", output);
             }
-            process::exit(0);
+            process::exit(if errors.is_empty() {
+                0
+            } else {
+                PARTIAL_FAILURE_EXIT_CODE
+            });
         }
         Err(err) => {
             eprintln!("{}", err);
@@ -28,54 +36,223 @@ fn is_atty_stdout() -> bool {
     atty::is(atty::Stream::Stdout)
 }
 
-/// A wrapper that does everything from user input to output.
-fn operate(matches: ArgMatches) -> Result<String, Box<dyn Error>> {
-    let text = get_text_from_input(&matches)?;
-    let convertor = wrap_text_with_converter(&matches, text)?;
-    output_as_string(matches, convertor)
+/// A wrapper that does everything from user input to output,
+/// along with the errors of files skipped in `--keep-going` mode.
+fn operate(
+    matches: ArgMatches,
+) -> Result<(String, Vec<String>), Box<dyn Error>> {
+    let (captures, errors) = capture_from_input(&matches)?;
+    let mut convertor = wrap_captures_with_converter(&matches, captures)?;
+    if matches.is_present("keep-going") && matches.is_present("json") {
+        convertor = convertor.report_errors(errors.clone());
+    } else {
+        for error in &errors {
+            eprintln!("{}", error);
+        }
+    }
+    Ok((output_as_string(matches, convertor)?, errors))
 }
 
-fn get_text_from_input(
+/// Capture words from input while reading it piece by piece,
+/// so that a large input isn't kept in memory as a whole.
+fn capture_from_input(
     matches: &ArgMatches,
-) -> Result<Vec<String>, Box<dyn Error>> {
-    let eof = matches.value_of("eof");
-    let text = match matches.values_of_lossy("files") {
+) -> Result<(Vec<Capture>, Vec<String>), Box<dyn Error>> {
+    let keep_going = matches.is_present("keep-going");
+    let mut errors = Vec::new();
+    let mut reader = build_reader(matches)?.keep_going(keep_going);
+    let captor = build_captor(matches)?;
+    let mut capturing = captor.capturing();
+    match matches.values_of_lossy("files") {
         None => {
             if is_atty_stdin() {
                 return Err(
                     "naming: no input was found. Enter -h or --help for help information.".into()
                 );
             } else {
-                vec![read_from_std_in(eof)?]
+                reader.capture_stdin(&mut capturing)?
             }
         }
-        Some(files) => read_from_files(&files, eof)?,
+        Some(paths) => {
+            let option =
+                |tag: &str| matches.values_of_lossy(tag).unwrap_or_default();
+            let mut walker = Walker::new()
+                .include(option("include"))
+                .exclude(option("exclude"))
+                .follow_links(matches.is_present("follow-links"))
+                .keep_going(keep_going);
+            let files = walker.files_of(&paths)?;
+            errors.extend_from_slice(walker.errors());
+            reader.capture_files(&files, &mut capturing)?
+        }
     };
-    Ok(text)
+    for warning in reader.warnings() {
+        eprintln!("{}", warning);
+    }
+    errors.extend_from_slice(reader.errors());
+    Ok((capturing.finish(), errors))
+}
+
+fn build_reader(matches: &ArgMatches) -> Result<Reader, Box<dyn Error>> {
+    let reader = Reader::new(None)
+        .markers(build_markers(matches)?)
+        .lossy(matches.is_present("lossy"));
+    match matches.value_of("encoding") {
+        Some(label) => Ok(reader.encoding(label)?),
+        None => Ok(reader),
+    }
+}
+
+fn build_markers(matches: &ArgMatches) -> Result<Markers, Box<dyn Error>> {
+    let mut markers = Markers::new(matches.is_present("regex-markers"));
+    if let Some(sof) = matches.value_of("sof") {
+        markers = markers.sof(sof)?;
+    }
+    if let Some(eof) = matches.value_of("eof") {
+        markers = markers.eof(eof)?;
+    }
+    for region in matches.values_of_lossy("region").unwrap_or_default() {
+        markers = markers.region(&region)?;
+    }
+    Ok(markers)
 }
 
 fn is_atty_stdin() -> bool {
     atty::is(atty::Stream::Stdin)
 }
 
-fn wrap_text_with_converter(
+fn wrap_captures_with_converter(
     matches: &ArgMatches,
-    text: Vec<String>,
+    captures: Vec<Capture>,
 ) -> Result<Convertor, Box<dyn Error>> {
     let option = |tag: &str| matches.values_of_lossy(tag);
+    // `--exclude-format` values are negated `--filter` values.
+    let filter = match (option("filter"), option("exclude-format")) {
+        (filter, None) => filter,
+        (filter, Some(excluded)) => Some(
+            filter
+                .unwrap_or_default()
+                .into_iter()
+                .chain(excluded.iter().map(|format| format!("!{}", format)))
+                .collect(),
+        ),
+    };
+
+    let digits = DigitPolicy::from_name(
+        matches.value_of("digits").unwrap_or("attach-previous"),
+    )?;
 
-    // text (String) --Captor--> words (Vec<String>)
-    // --Filter--> intermediate type instances (Vec<NamingCase>)
+    // words with positions (Vec<Capture>) --Stopwords--> project words
+    // --Filter--> intermediate type instances (Vec<Identifier>)
     // --> Convertor (ready to convert itself into different format outputs)
-    let convertor = Convertor::new(
+    let convertor = Convertor::from_identifiers(
         option("output"),
-        Filter::new(option("filter"))?.to_naming_cases_from(
-            Captor::new(option("locator"))?.capture_words(text),
-        ),
-    );
+        Filter::new(filter)?
+            .digits(digits)
+            .hungarian_prefixes(option("hungarian-prefixes"))
+            .dedup(Dedup::from_name(
+                matches.value_of("dedup").unwrap_or("exact"),
+            )?)
+            .length(
+                count_of(matches, "min-length")?,
+                count_of(matches, "max-length")?,
+            )
+            .words(
+                count_of(matches, "min-words")?,
+                count_of(matches, "max-words")?,
+            )
+            .skip_numbers(matches.is_present("skip-numbers"))
+            .match_patterns(&option("match").unwrap_or_default())?
+            .exclude_patterns(&option("exclude-match").unwrap_or_default())?
+            .to_identifiers_from(
+                build_stopwords(matches)?.remove_from(captures),
+            ),
+    )
+    .show_positions(matches.is_present("positions"))
+    .show_stats(matches.is_present("stats"))
+    .acronyms(option("acronyms").unwrap_or_default())
+    .digits(digits)
+    .strip_affixes(matches.is_present("strip-affixes"))
+    .sort_by_frequency(matches.is_present("sort-by-frequency"));
     Ok(convertor)
 }
 
+/// Parse the value of a counting option like `--min-length`.
+fn count_of(matches: &ArgMatches, name: &str) -> Result<Option<usize>, String> {
+    matches
+        .value_of(name)
+        .map(|value| {
+            value.parse().map_err(|_| {
+                format!(
+                    "naming: --{} `{}`: must be a non-negative integer.",
+                    name, value
+                )
+            })
+        })
+        .transpose()
+}
+
+fn build_captor(matches: &ArgMatches) -> Result<Captor, Box<dyn Error>> {
+    let lexer = build_lexer(matches)?;
+    let with_lexer = |captor: Captor| match lexer {
+        Some(lexer) => captor.lexer(lexer),
+        None => captor,
+    };
+    let captor =
+        match (matches.values_of_lossy("locator"), matches.value_of("lang")) {
+            (None, Some("auto")) => {
+                with_lexer(Captor::new(None)?.detect_language())
+            }
+            (None, Some(lang)) => {
+                with_lexer(Captor::from_language(Language::from_name(lang)?))
+            }
+            (None, None) => match lexer {
+                Some(lexer) => Captor::from_lexer(lexer),
+                None => Captor::new(None)?,
+            },
+            (locators, _) => with_lexer(Captor::new(locators)?),
+        };
+    let captor = match matches.value_of("word-pattern") {
+        Some(pattern) => captor.word_pattern(pattern)?,
+        None => captor,
+    };
+    Ok(captor
+        .keep_order(matches.is_present("keep-order"))
+        .pragmas(!matches.is_present("no-pragmas")))
+}
+
+fn build_stopwords(matches: &ArgMatches) -> Result<Stopwords, Box<dyn Error>> {
+    // keywords of `--lang` language (or detected languages with "auto")
+    // by default, other locators are chosen by user and kept as they are.
+    let keywords =
+        match (matches.value_of("keywords"), matches.value_of("lang")) {
+            (Some(keywords), _) => keywords,
+            (None, Some(lang)) => lang,
+            (None, None) => "none",
+        };
+    let mut stopwords = Stopwords::new().keywords(keywords)?;
+    for file in matches.values_of_lossy("exclude-words").unwrap_or_default() {
+        stopwords = stopwords.exclude_words_from(&file)?;
+    }
+    Ok(stopwords)
+}
+
+fn build_lexer(matches: &ArgMatches) -> Result<Option<Lexer>, Box<dyn Error>> {
+    let syntax = match matches.value_of("lexer") {
+        None => return Ok(None),
+        Some("auto") => None,
+        Some(name) => Some(Syntax::from_name(name)?),
+    };
+    let lexer = Lexer::new(syntax)
+        .include_comments(matches.is_present("include-comments"))
+        .include_strings(matches.is_present("include-strings"));
+    // keywords of `--lang` language rather than each file's language.
+    Ok(Some(match matches.value_of("lang") {
+        None | Some("auto") => lexer,
+        Some(lang) => lexer.language(Language::from_name(lang)?),
+    }))
+}
+
 fn output_as_string(
     matches: ArgMatches,
     convertor: Convertor,
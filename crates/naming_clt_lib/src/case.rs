@@ -0,0 +1,782 @@
+//! Unicode-aware detection and conversion of naming cases.
+//!
+//! Identifier characters follow UAX #31 (XID_Continue), and letters are
+//! cased with Unicode's default (locale-independent) mappings, so:
+//! * "ß" is upper-cased to "SS", and title-cased to "Ss";
+//! * Turkish "İ" is lower-cased to "i̇" (with a combining dot above),
+//!   and "ı" is upper-cased to "I";
+//! * caseless letters (like CJK characters) are treated as lowercase.
+
+use naming_lib::NamingCase;
+use unicode_xid::UnicodeXID;
+
+pub fn is_screaming_snake(identifier: &str) -> bool {
+    has_valid_parts(identifier, '_', |part| {
+        !part.chars().any(char::is_lowercase)
+    }) && identifier.chars().any(char::is_uppercase)
+}
+
+pub fn is_snake(identifier: &str) -> bool {
+    has_valid_parts(identifier, '_', |part| {
+        !part.chars().any(char::is_uppercase)
+    })
+}
+
+pub fn is_kebab(identifier: &str) -> bool {
+    has_valid_parts(identifier, '-', |part| {
+        !part.chars().any(char::is_uppercase)
+    })
+}
+
+pub fn is_camel(identifier: &str) -> bool {
+    is_one_part(identifier)
+        && has_letter(identifier)
+        && !identifier.chars().next().is_some_and(char::is_uppercase)
+}
+
+pub fn is_train(identifier: &str) -> bool {
+    has_valid_parts(identifier, '-', is_capitalized)
+}
+
+pub fn is_cobol(identifier: &str) -> bool {
+    has_valid_parts(identifier, '-', |part| {
+        !part.chars().any(char::is_lowercase)
+    }) && identifier.chars().any(char::is_uppercase)
+}
+
+pub fn is_dot(identifier: &str) -> bool {
+    has_valid_parts(identifier, '.', |part| {
+        !part.chars().any(char::is_uppercase)
+    })
+}
+
+pub fn is_path(identifier: &str) -> bool {
+    has_valid_parts(identifier, '/', |part| {
+        !part.chars().any(char::is_uppercase)
+    })
+}
+
+pub fn is_flat(identifier: &str) -> bool {
+    is_one_part(identifier)
+        && has_letter(identifier)
+        && !identifier.chars().any(char::is_uppercase)
+}
+
+pub fn is_upper_flat(identifier: &str) -> bool {
+    is_one_part(identifier)
+        && !identifier.chars().any(char::is_lowercase)
+        && identifier.chars().any(char::is_uppercase)
+}
+
+pub fn is_ada(identifier: &str) -> bool {
+    has_valid_parts(identifier, '_', is_capitalized)
+}
+
+/// Words separated by single spaces, like a heading "Page Size".
+pub fn is_title_case(identifier: &str) -> bool {
+    identifier.contains(' ') && has_valid_parts(identifier, ' ', is_capitalized)
+}
+
+/// Words separated by single spaces, like a label "Page size".
+pub fn is_sentence_case(identifier: &str) -> bool {
+    let mut parts = identifier.split(' ');
+    identifier.contains(' ')
+        && parts.next().is_some_and(is_capitalized)
+        && parts.all(|part| !part.chars().any(char::is_uppercase))
+        && has_valid_parts(identifier, ' ', |_| true)
+}
+
+pub fn is_pascal(identifier: &str) -> bool {
+    let mut chars = identifier.chars();
+    is_one_part(identifier)
+        && chars.next().is_some_and(char::is_uppercase)
+        // a single word in uppercase is screaming snake case
+        && (identifier.chars().count() == 1 || chars.any(char::is_lowercase))
+}
+
+/// Decimal numbers like "42" and "1_000", or hex, octal and binary
+/// literals like "0xFF", "0o17" and "0b1010",
+/// which some word patterns capture as words.
+pub fn is_numeric_literal(word: &str) -> bool {
+    let is_digits_of = |digits: &str, radix: u32| {
+        digits.chars().any(|c| c.is_digit(radix))
+            && digits.chars().all(|c| c == '_' || c.is_digit(radix))
+    };
+    match word.get(..2) {
+        Some("0x") | Some("0X") => is_digits_of(&word[2..], 16),
+        Some("0o") | Some("0O") => is_digits_of(&word[2..], 8),
+        Some("0b") | Some("0B") => is_digits_of(&word[2..], 2),
+        _ => is_digits_of(word, 10),
+    }
+}
+
+/// Split identifier into parts with given separator, check that
+/// there are no empty parts and every part satisfies the predicate.
+/// Words without any letter (like "1" and "1_000") are in no format.
+fn has_valid_parts(
+    identifier: &str,
+    separator: char,
+    predicate: impl Fn(&str) -> bool,
+) -> bool {
+    has_letter(identifier)
+        && identifier
+            .split(separator)
+            .all(|part| is_one_part(part) && predicate(part))
+}
+
+fn has_letter(identifier: &str) -> bool {
+    identifier.chars().any(char::is_alphabetic)
+}
+
+/// Starts with an uppercase letter, and the rest letters are lowercase.
+fn is_capitalized(part: &str) -> bool {
+    let mut chars = part.chars();
+    chars.next().is_some_and(char::is_uppercase)
+        && !chars.any(char::is_uppercase)
+}
+
+/// Non-empty, and only contains identifier characters except underscore.
+fn is_one_part(part: &str) -> bool {
+    !part.is_empty() && part.chars().all(is_word_char)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_xid_continue() && c != '_'
+}
+
+/// Naming formats that `Filter` detects, a superset of `NamingCase`'s
+/// cases, which can't represent formats like train case or title case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    SingleWord,
+    ScreamingSnake,
+    Snake,
+    Kebab,
+    Camel,
+    Pascal,
+    Train,
+    Cobol,
+    Dot,
+    Path,
+    Ada,
+    TitleCase,
+    SentenceCase,
+}
+
+/// Detect the format of a word with the same predicates that `Filter` uses,
+/// `None` if it's in no format, like "1" or "page_Size".
+pub fn which_format(identifier: &str) -> Option<Format> {
+    let format = if is_single_word(identifier) {
+        Format::SingleWord
+    } else if is_screaming_snake(identifier) {
+        Format::ScreamingSnake
+    } else if is_snake(identifier) {
+        Format::Snake
+    } else if is_kebab(identifier) {
+        Format::Kebab
+    } else if is_camel(identifier) {
+        Format::Camel
+    } else if is_pascal(identifier) {
+        Format::Pascal
+    } else if is_train(identifier) {
+        Format::Train
+    } else if is_cobol(identifier) {
+        Format::Cobol
+    } else if is_dot(identifier) {
+        Format::Dot
+    } else if is_path(identifier) {
+        Format::Path
+    } else if is_ada(identifier) {
+        Format::Ada
+    } else if is_title_case(identifier) {
+        Format::TitleCase
+    } else if is_sentence_case(identifier) {
+        Format::SentenceCase
+    } else {
+        return None;
+    };
+    Some(format)
+}
+
+/// Unicode-aware version of `naming_lib::which_case`, built on the same
+/// predicates that `Filter` uses, so the two always agree on a word.
+/// Formats that `NamingCase` can't represent are `NamingCase::Invalid`.
+pub fn which_case(identifier: &str) -> NamingCase {
+    let case = match which_format(identifier) {
+        Some(Format::SingleWord) => NamingCase::SingleWord,
+        Some(Format::ScreamingSnake) => NamingCase::ScreamingSnake,
+        Some(Format::Snake) => NamingCase::Snake,
+        Some(Format::Kebab) => NamingCase::Kebab,
+        Some(Format::Camel) => NamingCase::Camel,
+        Some(Format::Pascal) => NamingCase::Pascal,
+        _ => NamingCase::Invalid,
+    };
+    case(identifier.to_string())
+}
+
+/// A word in lowercase, uppercase or capitalized, like "size", "SIZE"
+/// and "Size", which starts with a letter.
+fn is_single_word(identifier: &str) -> bool {
+    identifier.chars().next().is_some_and(char::is_alphabetic)
+        && (is_flat(identifier)
+            || is_upper_flat(identifier)
+            || (is_one_part(identifier) && is_capitalized(identifier)))
+}
+
+/// Default prefixes of hungarian notation, type prefixes like "str"
+/// and scope prefixes (end with "_") like "m_".
+pub const HUNGARIAN_PREFIXES: [&str; 9] =
+    ["i", "str", "b", "p", "lp", "sz", "m_", "g_", "s_"];
+
+/// Split a hungarian notation identifier into its prefix and the rest,
+/// like "strName" -> ("str", "Name") and "m_lpszName" -> ("m_lpsz", "Name").
+/// A prefix is an optional scope prefix, followed by type prefixes
+/// which must be followed by an uppercase letter, so "is_valid"
+/// and "string" are not stripped.
+/// Return None if the identifier doesn't start with given prefixes.
+pub fn split_hungarian_prefix<'a, T: AsRef<str>>(
+    identifier: &'a str,
+    prefixes: &[T],
+) -> Option<(&'a str, &'a str)> {
+    let scope_len = prefixes
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|prefix| {
+            prefix.ends_with('_')
+                && identifier.len() > prefix.len()
+                && identifier.starts_with(prefix)
+        })
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    let prefix_len =
+        scope_len + type_prefix_len(&identifier[scope_len..], prefixes);
+    if prefix_len == 0 {
+        return None;
+    }
+    Some(identifier.split_at(prefix_len))
+}
+
+/// Length of the longest type prefixes (like "lpsz" in "lpszName")
+/// that are followed by an uppercase letter, 0 if there is none.
+fn type_prefix_len<T: AsRef<str>>(identifier: &str, prefixes: &[T]) -> usize {
+    prefixes
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|prefix| {
+            !prefix.is_empty()
+                && !prefix.ends_with('_')
+                && identifier.starts_with(prefix)
+        })
+        .filter_map(|prefix| {
+            let rest = &identifier[prefix.len()..];
+            if rest.chars().next().is_some_and(char::is_uppercase) {
+                return Some(prefix.len());
+            }
+            match type_prefix_len(rest, prefixes) {
+                0 => None,
+                len => Some(prefix.len() + len),
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Answer user's `--digits` option, decide which word a run of digits
+/// belongs to when words are split without separators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DigitPolicy {
+    /// "utf8Decode" -> ["utf8", "Decode"], "v2Api" -> ["v2", "Api"].
+    #[default]
+    AttachPrevious,
+    /// "utf8Decode" -> ["utf", "8", "Decode"], "v2Api" -> ["v", "2", "Api"].
+    Separate,
+    /// "utf8Decode" -> ["utf", "8Decode"], "v2Api" -> ["v", "2Api"].
+    AttachNext,
+}
+
+impl DigitPolicy {
+    pub fn from_name(name: &str) -> Result<DigitPolicy, String> {
+        match name {
+            "attach-previous" => Ok(DigitPolicy::AttachPrevious),
+            "separate" => Ok(DigitPolicy::Separate),
+            "attach-next" => Ok(DigitPolicy::AttachNext),
+            _ => {
+                Err(format!("naming: digit policy `{}`: no such policy.", name))
+            }
+        }
+    }
+}
+
+/// How identifiers are split into words and converted,
+/// shared by detection and all output formats.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaseOptions {
+    pub digits: DigitPolicy,
+    /// Words kept in uppercase in camel case and pascal case.
+    pub acronyms: Vec<String>,
+}
+
+/// Split identifier into words by separators and humps of camel case,
+/// words keep their origin letter cases.
+/// Acronyms are kept as words, like "HTTPServer" -> ["HTTP", "Server"]
+/// and "userID" -> ["user", "ID"], runs of digits are split by `digits`,
+/// explicit separators like "PAGE_2" are always kept.
+pub fn split_words(identifier: &str, digits: DigitPolicy) -> Vec<&str> {
+    let mut words = Vec::new();
+    for part in identifier.split(['_', '-', '.', '/', ' ']) {
+        let mut start = 0;
+        let mut previous: Option<char> = None;
+        for (i, c) in part.char_indices() {
+            let mut rest = part[i + c.len_utf8()..].chars();
+            let (next, after_next) = (rest.next(), rest.next());
+            let is_boundary = previous.is_some_and(|p| {
+                match (p.is_numeric(), c.is_numeric()) {
+                    (false, true) => digits != DigitPolicy::AttachPrevious,
+                    (true, false) => {
+                        digits == DigitPolicy::Separate
+                            || digits == DigitPolicy::AttachPrevious
+                                && c.is_uppercase()
+                    }
+                    // a hump is an uppercase letter after a non-uppercase
+                    // character, or the last uppercase letter of an acronym
+                    // before a lowercase one, except a plural "s" that ends
+                    // the acronym, like "IDs" in "userIDs".
+                    _ => {
+                        let is_plural = next == Some('s')
+                            && !after_next.is_some_and(char::is_lowercase);
+                        c.is_uppercase()
+                            && (!p.is_uppercase()
+                                || next.is_some_and(char::is_lowercase)
+                                    && !is_plural)
+                    }
+                }
+            });
+            if is_boundary {
+                words.push(&part[start..i]);
+                start = i;
+            }
+            previous = Some(c);
+        }
+        words.push(&part[start..]);
+    }
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// Split an identifier into its leading affix (underscores and sigils
+/// like "$" and "@"), its core and its trailing affix (underscores and
+/// predicate suffixes "?" and "!"), like "__init__" -> ("__", "init", "__"),
+/// "$scope" -> ("$", "scope", "") and "empty?" -> ("", "empty", "?").
+/// The whole identifier is the core if it's all affixes, like "__".
+pub fn split_affixes(identifier: &str) -> (&str, &str, &str) {
+    let core = identifier.trim_start_matches(['_', '$', '@']);
+    let leading = &identifier[..identifier.len() - core.len()];
+    let core_end = core.trim_end_matches(['_', '?', '!']).len();
+    if core_end == 0 {
+        return ("", identifier, "");
+    }
+    (leading, &core[..core_end], &core[core_end..])
+}
+
+pub fn to_screaming_snake(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "_", |_, word| word.to_uppercase())
+}
+
+pub fn to_snake(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "_", |_, word| word.to_lowercase())
+}
+
+pub fn to_kebab(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "-", |_, word| word.to_lowercase())
+}
+
+/// Words in `options.acronyms` (case-insensitively) are upper-cased
+/// except the first word, like "user_id" -> "userID".
+pub fn to_camel(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "", |i, word| {
+        if i == 0 {
+            word.to_lowercase()
+        } else {
+            capitalize_or_acronym(word, &options.acronyms)
+        }
+    })
+}
+
+/// Words in `options.acronyms` (case-insensitively) are upper-cased,
+/// like "xml_http_request" -> "XMLHttpRequest".
+pub fn to_pascal(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "", |_, word| {
+        capitalize_or_acronym(word, &options.acronyms)
+    })
+}
+
+pub fn to_train(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "-", |_, word| capitalize(word))
+}
+
+pub fn to_cobol(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "-", |_, word| word.to_uppercase())
+}
+
+pub fn to_dot(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, ".", |_, word| word.to_lowercase())
+}
+
+pub fn to_path(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "/", |_, word| word.to_lowercase())
+}
+
+pub fn to_flat(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "", |_, word| word.to_lowercase())
+}
+
+pub fn to_upper_flat(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "", |_, word| word.to_uppercase())
+}
+
+pub fn to_ada(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, "_", |_, word| capitalize(word))
+}
+
+pub fn to_title_case(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, " ", |_, word| capitalize(word))
+}
+
+pub fn to_sentence_case(identifier: &str, options: &CaseOptions) -> String {
+    join_words(identifier, options, " ", |i, word| {
+        if i == 0 {
+            capitalize(word)
+        } else {
+            word.to_lowercase()
+        }
+    })
+}
+
+/// Map each word (with its index) of the identifier's core, and join them
+/// with the separator, affixes (see `split_affixes`) are kept as they are.
+fn join_words(
+    identifier: &str,
+    options: &CaseOptions,
+    separator: &str,
+    mapper: impl Fn(usize, &str) -> String,
+) -> String {
+    let (leading, core, trailing) = split_affixes(identifier);
+    let words: Vec<String> = split_words(core, options.digits)
+        .into_iter()
+        .enumerate()
+        .map(|(i, word)| mapper(i, word))
+        .collect();
+    format!("{}{}{}", leading, words.join(separator), trailing)
+}
+
+/// A plural acronym keeps its lowercase "s", like "ids" -> "IDs".
+fn capitalize_or_acronym(word: &str, acronyms: &[String]) -> String {
+    let upper = word.to_uppercase();
+    let is_acronym = |word: &str| {
+        acronyms.iter().any(|acronym| acronym.to_uppercase() == word)
+    };
+    if is_acronym(&upper) {
+        upper
+    } else if word.ends_with('s') && is_acronym(&upper[..upper.len() - 1]) {
+        format!("{}s", &upper[..upper.len() - 1])
+    } else {
+        capitalize(word)
+    }
+}
+
+/// Title-case the first letter, and lower-case the rest,
+/// leading digits are kept, like "2api" -> "2Api".
+fn capitalize(word: &str) -> String {
+    let letters = word.trim_start_matches(char::is_numeric);
+    let mut chars = letters.chars();
+    match chars.next() {
+        None => word.to_string(),
+        Some(first) => {
+            word[..word.len() - letters.len()].to_string()
+                + &to_title(first)
+                + &chars.as_str().to_lowercase()
+        }
+    }
+}
+
+/// Title case differs from uppercase for a few letters,
+/// which `char::to_uppercase` doesn't know.
+fn to_title(c: char) -> String {
+    match c {
+        'ß' => "Ss".to_string(),
+        'Ǆ' | 'ǅ' | 'ǆ' => 'ǅ'.to_string(),
+        'Ǉ' | 'ǈ' | 'ǉ' => 'ǈ'.to_string(),
+        'Ǌ' | 'ǋ' | 'ǌ' => 'ǋ'.to_string(),
+        'Ǳ' | 'ǲ' | 'ǳ' => 'ǲ'.to_string(),
+        c => c.to_uppercase().collect(),
+    }
+}
+
+#[cfg(test)]
+mod case_tests {
+    use naming_lib::NamingCase;
+
+    use super::*;
+    use crate::to_string_vec;
+
+    #[test]
+    fn detect_unicode_cases() {
+        assert!(is_snake("größe_max"));
+        assert!(is_snake("变量_名"));
+        assert!(!is_screaming_snake("变量_名"));
+        assert!(is_screaming_snake("GRÖSSE_MAX"));
+        assert!(is_kebab("größe-max"));
+        assert!(is_camel("étatCourant"));
+        assert!(is_pascal("ÉtatCourant"));
+        assert!(!is_pascal("ÉTAT"));
+        assert!(!is_snake("-größe_"));
+    }
+
+    #[test]
+    fn detect_numeric_literals() {
+        for word in ["1", "1_000", "0xFF", "0X1f_2e", "0o17", "0b1010"] {
+            assert!(is_numeric_literal(word), "{}", word);
+        }
+        for word in ["x1", "0xG", "0x", "_", "1st", "0b12", "一"] {
+            assert!(!is_numeric_literal(word), "{}", word);
+        }
+    }
+
+    #[test]
+    fn which_unicode_case() {
+        let case = |word: &str| which_case(word);
+        assert_eq!(
+            case("ÉtatCourant"),
+            NamingCase::Pascal("ÉtatCourant".to_string())
+        );
+        assert_eq!(case("变量_名"), NamingCase::Snake("变量_名".to_string()));
+        assert_eq!(case("größe"), NamingCase::SingleWord("größe".to_string()));
+        // ascii words are classified by the same predicates
+        assert_eq!(case("Size"), NamingCase::SingleWord("Size".to_string()));
+        assert_eq!(case("pageSize"), NamingCase::Camel("pageSize".to_string()));
+        assert_eq!(case("1"), NamingCase::Invalid("1".to_string()));
+        assert_eq!(
+            case("Content-Type"),
+            NamingCase::Invalid("Content-Type".to_string())
+        );
+    }
+
+    #[test]
+    fn which_formats() {
+        assert_eq!(which_format("Content-Type"), Some(Format::Train));
+        assert_eq!(which_format("CONTENT-TYPE"), Some(Format::Cobol));
+        assert_eq!(which_format("log.level"), Some(Format::Dot));
+        assert_eq!(which_format("api/users"), Some(Format::Path));
+        assert_eq!(which_format("Page_Size"), Some(Format::Ada));
+        assert_eq!(which_format("Page Size"), Some(Format::TitleCase));
+        assert_eq!(which_format("Page size"), Some(Format::SentenceCase));
+        assert_eq!(which_format("pageSize"), Some(Format::Camel));
+        // digits only words are in no format
+        assert_eq!(which_format("1"), None);
+        assert_eq!(which_format("1_000"), None);
+        assert!(!is_snake("1") && !is_camel("1") && !is_flat("1"));
+    }
+
+    #[test]
+    fn detect_additional_cases() {
+        assert!(is_train("Content-Type"));
+        assert!(!is_train("Content-type"));
+        assert!(is_cobol("CONTENT-TYPE"));
+        assert!(!is_cobol("CONTENT_TYPE"));
+        assert!(is_dot("log.level"));
+        assert!(!is_dot("log..level"));
+        assert!(is_path("api/users"));
+        assert!(!is_path("/api/users"));
+        assert!(is_flat("username"));
+        assert!(!is_flat("user_name"));
+        assert!(is_upper_flat("USERNAME"));
+        assert!(is_ada("Page_Size"));
+        assert!(!is_ada("Page_size"));
+    }
+
+    #[test]
+    fn detect_phrases() {
+        assert!(is_title_case("Page Size"));
+        assert!(!is_title_case("Page size"));
+        assert!(!is_title_case("Page  Size"));
+        assert!(is_sentence_case("Page size"));
+        assert!(!is_sentence_case("Page Size"));
+        assert!(!is_sentence_case("page size"));
+        assert!(!is_sentence_case("Page size "));
+        // a single word is not a phrase
+        assert!(!is_title_case("Page"));
+        assert!(!is_sentence_case("Page"));
+    }
+
+    #[test]
+    fn convert_to_phrases() {
+        let options = CaseOptions::default();
+        assert_eq!(to_title_case("page_size", &options), "Page Size");
+        assert_eq!(to_sentence_case("pageSize", &options), "Page size");
+        assert_eq!(to_sentence_case("größe_max", &options), "Größe max");
+        // and back to identifiers.
+        assert_eq!(to_snake("Page size", &options), "page_size");
+        assert_eq!(to_camel("Page Size", &options), "pageSize");
+    }
+
+    #[test]
+    fn convert_to_additional_cases() {
+        let options = CaseOptions::default();
+        let word = "pageSize";
+        assert_eq!(to_train(word, &options), "Page-Size");
+        assert_eq!(to_cobol(word, &options), "PAGE-SIZE");
+        assert_eq!(to_dot(word, &options), "page.size");
+        assert_eq!(to_path(word, &options), "page/size");
+        assert_eq!(to_flat(word, &options), "pagesize");
+        assert_eq!(to_upper_flat(word, &options), "PAGESIZE");
+        assert_eq!(to_ada(word, &options), "Page_Size");
+        assert_eq!(to_snake("Content-Type", &options), "content_type");
+        assert_eq!(to_camel("log.level", &options), "logLevel");
+        assert_eq!(to_pascal("api/user_id", &options), "ApiUserId");
+    }
+
+    #[test]
+    fn split_and_keep_affixes() {
+        assert_eq!(split_affixes("__init__"), ("__", "init", "__"));
+        assert_eq!(split_affixes("_private"), ("_", "private", ""));
+        assert_eq!(split_affixes("name_"), ("", "name", "_"));
+        assert_eq!(split_affixes("@@classVar"), ("@@", "classVar", ""));
+        assert_eq!(split_affixes("save!"), ("", "save", "!"));
+        assert_eq!(split_affixes("__"), ("", "__", ""));
+        let options = CaseOptions::default();
+        assert_eq!(to_snake("_privateName", &options), "_private_name");
+        assert_eq!(
+            to_pascal("__dunder_method__", &options),
+            "__DunderMethod__"
+        );
+        assert_eq!(to_screaming_snake("$scope", &options), "$SCOPE");
+        assert_eq!(to_kebab("@ivarName", &options), "@ivar-name");
+    }
+
+    #[test]
+    fn split_hungarian_prefixes() {
+        let split = |word: &'static str| {
+            split_hungarian_prefix(word, &HUNGARIAN_PREFIXES)
+        };
+        assert_eq!(split("strÉtatCourant"), Some(("str", "ÉtatCourant")));
+        assert_eq!(split("lpszName"), Some(("lpsz", "Name")));
+        assert_eq!(split("m_pBuffer"), Some(("m_p", "Buffer")));
+        assert_eq!(split("g_count"), Some(("g_", "count")));
+        assert_eq!(split("userName"), None);
+        assert_eq!(split("is_valid"), None);
+        assert_eq!(split("string"), None);
+        assert_eq!(split("m_"), None);
+        assert_eq!(
+            split_hungarian_prefix("intSize", &["int"]),
+            Some(("int", "Size"))
+        );
+    }
+
+    #[test]
+    fn split_acronyms() {
+        let options = CaseOptions::default();
+        let split =
+            |word: &'static str| split_words(word, DigitPolicy::AttachPrevious);
+        assert_eq!(split("HTTPServer"), vec!["HTTP", "Server"]);
+        assert_eq!(split("parseXMLDocument"), vec!["parse", "XML", "Document"]);
+        assert_eq!(split("userID"), vec!["user", "ID"]);
+        assert_eq!(split("userIDs"), vec!["user", "IDs"]);
+        assert_eq!(split("URLsToIDs"), vec!["URLs", "To", "IDs"]);
+        assert_eq!(split("ÉTATCourant"), vec!["ÉTAT", "Courant"]);
+        assert_eq!(to_snake("HTTPServer", &options), "http_server");
+        assert_eq!(
+            to_kebab("parseXMLDocument", &options),
+            "parse-xml-document"
+        );
+        assert_eq!(to_camel("userID", &options), "userId");
+    }
+
+    #[test]
+    fn convert_with_acronyms() {
+        let options = CaseOptions {
+            acronyms: to_string_vec(vec!["ID", "url", "XML"]),
+            ..CaseOptions::default()
+        };
+        assert_eq!(to_camel("user_id", &options), "userID");
+        assert_eq!(to_camel("userIDs", &options), "userIDs");
+        assert_eq!(to_snake("userIDs", &options), "user_ids");
+        assert_eq!(to_camel("url_path", &options), "urlPath");
+        assert_eq!(to_pascal("xml_http_request", &options), "XMLHttpRequest");
+        assert_eq!(to_pascal("BaseURL", &options), "BaseURL");
+        assert_eq!(to_pascal("identity", &options), "Identity");
+    }
+
+    #[test]
+    fn split_unicode_words() {
+        let split =
+            |word: &'static str| split_words(word, DigitPolicy::AttachPrevious);
+        assert_eq!(split("ÉtatCourant"), vec!["État", "Courant"]);
+        assert_eq!(split("变量_名"), vec!["变量", "名"]);
+        assert_eq!(split("größeMax"), vec!["größe", "Max"]);
+    }
+
+    #[test]
+    fn split_digits_with_policies() {
+        let split_with =
+            |digits| move |word: &'static str| split_words(word, digits);
+        let split = split_with(DigitPolicy::AttachPrevious);
+        assert_eq!(split("utf8Decode"), vec!["utf8", "Decode"]);
+        assert_eq!(split("v2Api"), vec!["v2", "Api"]);
+        assert_eq!(split("PAGE_2"), vec!["PAGE", "2"]);
+        let split = split_with(DigitPolicy::Separate);
+        assert_eq!(split("utf8Decode"), vec!["utf", "8", "Decode"]);
+        assert_eq!(split("x86_64"), vec!["x", "86", "64"]);
+        let split = split_with(DigitPolicy::AttachNext);
+        assert_eq!(split("utf8Decode"), vec!["utf", "8Decode"]);
+        assert_eq!(split("v2Api"), vec!["v", "2Api"]);
+        assert_eq!(split("PAGE_2"), vec!["PAGE", "2"]);
+    }
+
+    #[test]
+    fn convert_round_trip_with_digit_policies() {
+        let with = |digits| CaseOptions { digits, ..CaseOptions::default() };
+        let options = with(DigitPolicy::AttachPrevious);
+        assert_eq!(to_snake("v2Api", &options), "v2_api");
+        assert_eq!(to_camel("v2_api", &options), "v2Api");
+        assert_eq!(to_snake("page2", &options), "page2");
+        let options = with(DigitPolicy::Separate);
+        assert_eq!(to_snake("utf8Decode", &options), "utf_8_decode");
+        assert_eq!(to_camel("utf_8_decode", &options), "utf8Decode");
+        assert_eq!(to_snake(&to_camel("PAGE_2", &options), &options), "page_2");
+        let options = with(DigitPolicy::AttachNext);
+        assert_eq!(to_snake("v2Api", &options), "v_2api");
+        assert_eq!(to_camel("v_2api", &options), "v2Api");
+        assert_eq!(to_pascal("v_2api", &options), "V2Api");
+    }
+
+    #[test]
+    fn parse_digit_policies() {
+        assert_eq!(
+            DigitPolicy::from_name("attach-next"),
+            Ok(DigitPolicy::AttachNext)
+        );
+        assert!(DigitPolicy::from_name("next").is_err());
+    }
+
+    #[test]
+    fn convert_with_special_casing() {
+        let options = CaseOptions::default();
+        assert_eq!(to_screaming_snake("größe", &options), "GRÖSSE");
+        assert_eq!(to_pascal("ßa_ǆb", &options), "Ssaǅb");
+        assert_eq!(to_snake("İSTANBUL_ılık", &options), "i\u{307}stanbul_ılık");
+        assert_eq!(to_screaming_snake("ılık", &options), "ILIK");
+        assert_eq!(to_camel("ÉtatCourant", &options), "étatCourant");
+        assert_eq!(to_kebab("变量_名", &options), "变量-名");
+    }
+
+    #[test]
+    fn convert_ascii_as_before() {
+        let options = CaseOptions::default();
+        assert_eq!(to_screaming_snake("camelCase", &options), "CAMEL_CASE");
+        assert_eq!(to_snake("PascalCase", &options), "pascal_case");
+        assert_eq!(to_kebab("SCREAMING_SNAKE", &options), "screaming-snake");
+        assert_eq!(to_camel("kebab-case", &options), "kebabCase");
+        assert_eq!(to_pascal("snake_case", &options), "SnakeCase");
+    }
+}
@@ -1,81 +1,459 @@
+use std::cmp::Reverse;
 use std::collections::HashMap;
 
-use naming_lib::{self as naming, NamingCase};
+use fancy_regex::Regex;
+use naming_lib::NamingCase;
 
 use super::Formatter;
+use crate::case::{self, CaseOptions, DigitPolicy, Format};
+use crate::extraction::{Capture, Position};
+
+/// A captured word that has been converted to NamingCase,
+/// along with the positions of its occurrences in input,
+/// the placeholder names of locators that captured it,
+/// all spellings of it when deduplicated with a normalized mode,
+/// the hungarian notation prefix stripped from it,
+/// and the leading and trailing affixes (like "__" and "$") stripped from it.
+#[derive(Debug, PartialEq)]
+pub struct Identifier {
+    pub case: NamingCase,
+    /// Detected format, also for formats that `NamingCase` can't represent.
+    pub format: Option<Format>,
+    pub positions: Vec<Position>,
+    pub tags: Vec<String>,
+    pub spellings: Vec<String>,
+    pub prefix: Option<String>,
+    pub affixes: (String, String),
+}
+
+impl Identifier {
+    /// How many times this identifier occurs in input.
+    pub fn occurrences(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// The word with its affixes, like "__init__".
+    pub fn word(&self) -> String {
+        let (leading, trailing) = &self.affixes;
+        format!("{}{}{}", leading, self.case, trailing)
+    }
+
+    /// The word as it's captured, with its affixes and hungarian prefix,
+    /// like "_strName".
+    pub fn captured_word(&self) -> String {
+        let (leading, trailing) = &self.affixes;
+        let prefix = self.prefix.as_deref().unwrap_or_default();
+        format!("{}{}{}{}", leading, prefix, self.case, trailing)
+    }
+
+    /// Origin string in normal output formats,
+    /// all spellings are joined with commas like "pageSize,page_size".
+    pub fn origin(&self) -> String {
+        if self.spellings.is_empty() {
+            self.captured_word()
+        } else {
+            self.spellings.join(",")
+        }
+    }
+
+    /// In how many files this identifier occurs, stdin counts as one file.
+    pub fn files(&self) -> usize {
+        let mut paths: Vec<&Option<String>> =
+            self.positions.iter().map(|position| &position.path).collect();
+        paths.sort();
+        paths.dedup();
+        paths.len()
+    }
+}
+
+impl From<NamingCase> for Identifier {
+    fn from(case: NamingCase) -> Self {
+        Identifier {
+            format: case::which_format(&case.to_string()),
+            case,
+            positions: Vec::new(),
+            tags: Vec::new(),
+            spellings: Vec::new(),
+            prefix: None,
+            affixes: (String::new(), String::new()),
+        }
+    }
+}
+
+/// Answer user's `--dedup` option, decide which identifiers
+/// are treated as the same one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dedup {
+    /// Same strings, e.g. "pageSize" and "pageSize".
+    Exact,
+    /// Same strings ignoring letter cases, e.g. "pageSize" and "PageSize".
+    IgnoreCase,
+    /// Same word sequences, e.g. "pageSize", "page_size" and "PAGE_SIZE".
+    Words,
+}
+
+impl Dedup {
+    pub fn from_name(name: &str) -> Result<Dedup, String> {
+        match name {
+            "exact" => Ok(Dedup::Exact),
+            "ignore-case" => Ok(Dedup::IgnoreCase),
+            "words" => Ok(Dedup::Words),
+            _ => Err(format!("naming: dedup mode `{}`: no such mode.", name)),
+        }
+    }
+
+    fn key_of(&self, word: &str, digits: DigitPolicy) -> String {
+        match self {
+            Dedup::Exact => word.to_string(),
+            Dedup::IgnoreCase => word.to_lowercase(),
+            Dedup::Words => case::to_snake(
+                word,
+                &CaseOptions { digits, ..CaseOptions::default() },
+            ),
+        }
+    }
+}
 
 /// Answer user's `--filter` option,
 /// ignore captured words that user not indicates in `--filter` option,
 /// and convert words to NamingCase instances.
 pub struct Filter {
     options: Vec<String>,
+    dedup: Dedup,
+    // Inclusive bounds of the count of characters,
+    // and the count of words after splitting.
+    length: (Option<usize>, Option<usize>),
+    words: (Option<usize>, Option<usize>),
+    skip_numbers: bool,
+    // Words must match one of `matches` (if any),
+    // and none of `excludes`.
+    matches: Vec<Regex>,
+    excludes: Vec<Regex>,
+    hungarian_prefixes: Vec<String>,
+    digits: DigitPolicy,
 }
 
 impl Filter {
+    /// Options are format names (see `PREDICATES`) and "h"
+    /// (hungarian notation, see `hungarian_prefixes`) to keep,
+    /// and negated names like "!k" to drop, see `resolve_negations`.
     pub fn new(options: Option<Vec<String>>) -> Result<Filter, &'static str> {
-        let options = options.unwrap_or_else(|| super::DEFAULT_OPTIONS.clone());
+        let options = options.unwrap_or_else(|| super::DEFAULT_FILTER.clone());
+        let (negated, options): (Vec<String>, Vec<String>) =
+            options.into_iter().partition(|option| option.starts_with('!'));
+        let options = Filter::resolve_negations(options, &negated);
+        if options.is_empty() {
+            return Err(
+                "naming: In option --filter, all formats are excluded.",
+            );
+        }
+        Ok(Filter {
+            options,
+            dedup: Dedup::Exact,
+            length: (None, None),
+            words: (None, None),
+            skip_numbers: false,
+            matches: Vec::new(),
+            excludes: Vec::new(),
+            hungarian_prefixes: crate::to_string_vec(
+                case::HUNGARIAN_PREFIXES.to_vec(),
+            ),
+            digits: DigitPolicy::default(),
+        })
+    }
+
+    /// Group identifiers that are the same in given mode into one,
+    /// which keeps all spellings of them (except in `Dedup::Exact` mode),
+    /// see `to_identifiers_from`.
+    pub fn dedup(mut self, mode: Dedup) -> Filter {
+        self.dedup = mode;
+        self
+    }
+
+    /// Answer user's `--digits` option, split words with the policy
+    /// when counting words and grouping identifiers in `Dedup::Words` mode.
+    pub fn digits(mut self, policy: DigitPolicy) -> Filter {
+        self.digits = policy;
+        self
+    }
+
+    /// Answer user's `--min-length` and `--max-length` options,
+    /// only keep words whose count of characters is in given bounds.
+    pub fn length(mut self, min: Option<usize>, max: Option<usize>) -> Filter {
+        self.length = (min, max);
+        self
+    }
+
+    /// Answer user's `--min-words` and `--max-words` options,
+    /// only keep identifiers whose count of words (split by separators
+    /// and humps of camel case) is in given bounds.
+    pub fn words(mut self, min: Option<usize>, max: Option<usize>) -> Filter {
+        self.words = (min, max);
+        self
+    }
+
+    /// Answer user's `--skip-numbers` flag, drop numeric literals
+    /// like "42" and "0xFF" (see `case::is_numeric_literal`).
+    pub fn skip_numbers(mut self, skip: bool) -> Filter {
+        self.skip_numbers = skip;
+        self
+    }
+
+    /// Answer user's `--match` option, only keep words that match
+    /// one of given regexes (with fancy-regex syntax, same as locators),
+    /// like "Id$". Regexes search anywhere in words unless anchored.
+    pub fn match_patterns<T: AsRef<str>>(
+        mut self,
+        patterns: &[T],
+    ) -> Result<Filter, String> {
+        self.matches = Filter::build_regexes(patterns)?;
+        Ok(self)
+    }
+
+    /// Answer user's `--exclude-match` option, drop words that match
+    /// one of given regexes, like "^test_".
+    pub fn exclude_patterns<T: AsRef<str>>(
+        mut self,
+        patterns: &[T],
+    ) -> Result<Filter, String> {
+        self.excludes = Filter::build_regexes(patterns)?;
+        Ok(self)
+    }
 
-        if Filter::has_hungarian_camel_conflict(&options) {
-            return Err("naming: In option --filter, at most one of the two, \
-            hungarian notation (h) and camel case (c) can appear.");
+    fn build_regexes<T: AsRef<str>>(
+        patterns: &[T],
+    ) -> Result<Vec<Regex>, String> {
+        patterns
+            .iter()
+            .map(|pattern| {
+                let pattern = pattern.as_ref();
+                Regex::new(pattern).map_err(|msg| {
+                    format!("naming: pattern `{}`: {}", pattern, msg)
+                })
+            })
+            .collect()
+    }
+
+    /// Answer user's `--hungarian-prefixes` option, with "h" in options,
+    /// words starting with one of given prefixes (see
+    /// `case::split_hungarian_prefix`) are kept, and the prefixes
+    /// are stripped before conversion, like "strName" -> "Name".
+    /// Default (also on `None`): `case::HUNGARIAN_PREFIXES`.
+    pub fn hungarian_prefixes(
+        mut self,
+        prefixes: Option<Vec<String>>,
+    ) -> Filter {
+        if let Some(prefixes) = prefixes {
+            self.hungarian_prefixes = prefixes;
         }
-        Ok(Filter { options })
+        self
     }
 
-    fn has_hungarian_camel_conflict(options: &[String]) -> bool {
-        options.contains(&"h".to_string()) && options.contains(&"c".to_string())
+    /// Without negated options, keep listed formats as they are.
+    /// Otherwise, drop negated formats from the listed formats,
+    /// or from all formats if only hungarian notation (or nothing) is listed,
+    /// like "h,!k" means "S,s,c,p,h".
+    fn resolve_negations(
+        options: Vec<String>,
+        negated: &[String],
+    ) -> Vec<String> {
+        if negated.is_empty() {
+            return options;
+        }
+        let hungarian = "h".to_string();
+        let requires_hungarian = options.contains(&hungarian);
+        let mut resolved: Vec<String> =
+            options.into_iter().filter(|option| *option != hungarian).collect();
+        if resolved.is_empty() {
+            resolved = super::DEFAULT_FILTER.clone();
+        }
+        if requires_hungarian {
+            resolved.push(hungarian);
+        }
+        resolved.retain(|option| !negated.contains(&format!("!{}", option)));
+        resolved
     }
 
     /// Not only transform String to NamingCase,
     /// but also apply given filter on result vector.
     pub fn to_naming_cases_from(&self, words: Vec<String>) -> Vec<NamingCase> {
-        let words = self.filter_words_with_options(words);
-        self.convert_words_to_naming_cases(words)
+        let predicates = self.selected_predicates();
+        words
+            .iter()
+            .filter_map(|word| self.split_accepted(&predicates, word))
+            .map(|parts| case::which_case(parts.word))
+            .collect()
     }
 
-    fn filter_words_with_options(&self, mut words: Vec<String>) -> Vec<String> {
-        let predicates: Vec<fn(&str) -> bool> = Filter::PREDICATES
-            .iter()
-            .filter(|predicate| {
-                self.options.contains(&predicate.name.to_string())
+    /// Same as `to_naming_cases_from`,
+    /// but keep the positions and tags of captures in result,
+    /// and group identifiers with the dedup mode.
+    pub fn to_identifiers_from(
+        &self,
+        captures: Vec<Capture>,
+    ) -> Vec<Identifier> {
+        let predicates = self.selected_predicates();
+        let identifiers = captures
+            .into_iter()
+            .filter_map(|capture| {
+                let parts = self.split_accepted(&predicates, &capture.word)?;
+                Some(Identifier {
+                    case: case::which_case(parts.word),
+                    format: case::which_format(parts.word),
+                    prefix: parts.prefix.map(str::to_string),
+                    affixes: (
+                        parts.leading.to_string(),
+                        parts.trailing.to_string(),
+                    ),
+                    positions: capture.positions,
+                    tags: capture.tags,
+                    spellings: Vec::new(),
+                })
             })
-            .map(|predicate| predicate.function)
             .collect();
-
-        words.retain(|word| Filter::is_one_of_formats(&predicates, word));
-        words
+        self.group_identifiers(identifiers)
     }
 
-    const PREDICATES: [Predicate; 6] = [
-        Predicate { name: "S", function: naming::is_screaming_snake },
-        Predicate { name: "s", function: naming::is_snake },
-        Predicate { name: "k", function: naming::is_kebab },
-        Predicate { name: "c", function: naming::is_camel },
-        Predicate { name: "h", function: naming::is_camel },
-        Predicate { name: "p", function: naming::is_pascal },
-    ];
+    /// Merge identifiers with the same key into the first one of them,
+    /// keep the order of first identifiers.
+    fn group_identifiers(
+        &self,
+        identifiers: Vec<Identifier>,
+    ) -> Vec<Identifier> {
+        if self.dedup == Dedup::Exact {
+            // captures are already deduplicated by exact strings.
+            return identifiers;
+        }
+        let mut groups: Vec<Identifier> = Vec::new();
+        let mut indexes: HashMap<String, usize> = HashMap::new();
+        for identifier in identifiers {
+            // hungarian notations are grouped by their stripped words.
+            let key = self.dedup.key_of(&identifier.word(), self.digits);
+            let spelling = identifier.captured_word();
+            match indexes.get(&key) {
+                None => {
+                    indexes.insert(key, groups.len());
+                    groups.push(Identifier {
+                        spellings: vec![spelling],
+                        ..identifier
+                    });
+                }
+                Some(index) => {
+                    let group = &mut groups[*index];
+                    if !group.spellings.contains(&spelling) {
+                        group.spellings.push(spelling);
+                    }
+                    for tag in identifier.tags {
+                        if !group.tags.contains(&tag) {
+                            group.tags.push(tag);
+                        }
+                    }
+                    group.positions.extend(identifier.positions);
+                }
+            }
+        }
+        for group in groups.iter_mut() {
+            Filter::sort_positions(&mut group.positions);
+        }
+        groups
+    }
 
-    fn is_one_of_formats(predicates: &[fn(&str) -> bool], word: &str) -> bool {
-        predicates.iter().map(|f| f(word)).reduce(|a, b| a || b).unwrap()
+    /// Sort positions by offsets in each file,
+    /// files keep the order of their first appearances.
+    fn sort_positions(positions: &mut [Position]) {
+        let mut files: HashMap<Option<String>, usize> = HashMap::new();
+        for position in positions.iter() {
+            let next = files.len();
+            files.entry(position.path.clone()).or_insert(next);
+        }
+        positions
+            .sort_by_key(|position| (files[&position.path], position.offset));
     }
 
-    fn convert_words_to_naming_cases(
+    /// Split the word into parts if it's accepted by the filter,
+    /// the hungarian prefix is split only once for each word.
+    fn split_accepted<'a>(
         &self,
-        words: Vec<String>,
-    ) -> Vec<NamingCase> {
-        // if user wants to treat camel case words as hungarian notation format.
-        let required_hungarian = self.options.contains(&"h".to_string());
-        words
+        predicates: &[fn(&str) -> bool],
+        word: &'a str,
+    ) -> Option<Parts<'a>> {
+        let is_within = |(min, max): (Option<usize>, Option<usize>), count| {
+            !matches!(min, Some(min) if count < min)
+                && !matches!(max, Some(max) if count > max)
+        };
+        // formats are detected without affixes.
+        let (leading, core, trailing) = case::split_affixes(word);
+        let accepted = !(self.skip_numbers && case::is_numeric_literal(word))
+            && is_within(self.length, word.chars().count())
+            && is_within(
+                self.words,
+                case::split_words(core, self.digits).len(),
+            )
+            && (self.matches.is_empty()
+                || Filter::matches_any(&self.matches, word))
+            && !Filter::matches_any(&self.excludes, word);
+        if !accepted {
+            return None;
+        }
+        let (prefix, word) = match self.split_hungarian(core) {
+            Some((prefix, rest)) => (Some(prefix), rest),
+            None if Filter::is_one_of_formats(predicates, core) => (None, core),
+            None => return None,
+        };
+        Some(Parts { leading, prefix, word, trailing })
+    }
+
+    /// Prefix and the rest of a hungarian notation word, if "h" is in options
+    /// and the rest is in one of the formats.
+    fn split_hungarian<'a>(&self, word: &'a str) -> Option<(&'a str, &'a str)> {
+        if !self.options.iter().any(|option| option == "h") {
+            return None;
+        }
+        case::split_hungarian_prefix(word, &self.hungarian_prefixes).filter(
+            |(_, rest)| {
+                Filter::PREDICATES
+                    .iter()
+                    .any(|predicate| (predicate.function)(rest))
+            },
+        )
+    }
+
+    /// A regex that fails (like exceeding the backtrack limit)
+    /// is treated as not matched.
+    fn matches_any(regexes: &[Regex], word: &str) -> bool {
+        regexes.iter().any(|regex| regex.is_match(word).unwrap_or(false))
+    }
+
+    fn selected_predicates(&self) -> Vec<fn(&str) -> bool> {
+        Filter::PREDICATES
             .iter()
-            .map(|word| {
-                if required_hungarian && naming::is_camel(word) {
-                    naming::from_hungarian_notation(word)
-                } else {
-                    naming::which_case(word)
-                }
+            .filter(|predicate| {
+                self.options.contains(&predicate.name.to_string())
             })
+            .map(|predicate| predicate.function)
             .collect()
     }
+
+    const PREDICATES: [Predicate; 14] = [
+        Predicate { name: "S", function: case::is_screaming_snake },
+        Predicate { name: "s", function: case::is_snake },
+        Predicate { name: "k", function: case::is_kebab },
+        Predicate { name: "c", function: case::is_camel },
+        Predicate { name: "p", function: case::is_pascal },
+        Predicate { name: "T", function: case::is_train },
+        Predicate { name: "K", function: case::is_cobol },
+        Predicate { name: "d", function: case::is_dot },
+        Predicate { name: "r", function: case::is_path },
+        Predicate { name: "f", function: case::is_flat },
+        Predicate { name: "F", function: case::is_upper_flat },
+        Predicate { name: "A", function: case::is_ada },
+        Predicate { name: "t", function: case::is_title_case },
+        Predicate { name: "e", function: case::is_sentence_case },
+    ];
+
+    fn is_one_of_formats(predicates: &[fn(&str) -> bool], word: &str) -> bool {
+        predicates.iter().any(|f| f(word))
+    }
 }
 
 // Intermediate type for filtering
@@ -85,21 +463,149 @@ struct Predicate {
     pub function: fn(&str) -> bool,
 }
 
+// Intermediate type for converting accepted words,
+// the word to convert is without affixes and hungarian prefix.
+struct Parts<'a> {
+    leading: &'a str,
+    prefix: Option<&'a str>,
+    word: &'a str,
+    trailing: &'a str,
+}
+
 /// Take `--output` option's value,
 /// convert matches extracted form input
 /// to different output formats for printing.
 pub struct Convertor {
     options: Vec<String>,
-    cases: Vec<NamingCase>,
+    identifiers: Vec<Identifier>,
+    show_positions: bool,
+    show_stats: bool,
+    case_options: CaseOptions,
+    strip_affixes: bool,
+    // None means don't output the "errors" field.
+    errors: Option<Vec<String>>,
 }
 
 impl Convertor {
     pub fn new(
         options: Option<Vec<String>>,
         cases: Vec<NamingCase>,
+    ) -> Convertor {
+        let identifiers = cases.into_iter().map(Identifier::from).collect();
+        Convertor::from_identifiers(options, identifiers)
+    }
+
+    pub fn from_identifiers(
+        options: Option<Vec<String>>,
+        identifiers: Vec<Identifier>,
     ) -> Convertor {
         let options = options.unwrap_or_else(|| super::DEFAULT_OPTIONS.clone());
-        Convertor { options, cases }
+        Convertor {
+            options,
+            identifiers,
+            show_positions: false,
+            show_stats: false,
+            case_options: CaseOptions::default(),
+            strip_affixes: false,
+            errors: None,
+        }
+    }
+
+    /// Answer user's `--positions` flag, print all occurrences of
+    /// each identifier in normal and json output formats.
+    pub fn show_positions(mut self, show: bool) -> Convertor {
+        self.show_positions = show;
+        self
+    }
+
+    /// Answer user's `--stats` flag, print how many times and in how many
+    /// files each identifier occurs in all output formats.
+    pub fn show_stats(mut self, show: bool) -> Convertor {
+        self.show_stats = show;
+        self
+    }
+
+    /// Answer user's `--acronyms` option, keep these words (matched
+    /// case-insensitively) in uppercase when converting to camel case
+    /// and pascal case, like "userID" and "XMLHttpRequest".
+    pub fn acronyms(mut self, acronyms: Vec<String>) -> Convertor {
+        self.case_options.acronyms = acronyms;
+        self
+    }
+
+    /// Answer user's `--digits` option, split words with the policy
+    /// in all output formats.
+    pub fn digits(mut self, policy: DigitPolicy) -> Convertor {
+        self.case_options.digits = policy;
+        self
+    }
+
+    /// Answer user's `--strip-affixes` flag, don't re-apply the leading
+    /// and trailing affixes (like "__" and "$") to converted words,
+    /// origin strings keep them anyway.
+    pub fn strip_affixes(mut self, strip: bool) -> Convertor {
+        self.strip_affixes = strip;
+        self
+    }
+
+    /// The word to convert, with or without its affixes.
+    fn word_to_convert(&self, identifier: &Identifier) -> String {
+        if self.strip_affixes {
+            identifier.case.to_string()
+        } else {
+            identifier.word()
+        }
+    }
+
+    /// Answer user's `--sort-by-frequency` flag, let identifiers that
+    /// occur more times (then in more files) come first,
+    /// identifiers with same frequency keep their order.
+    pub fn sort_by_frequency(mut self, sort: bool) -> Convertor {
+        if sort {
+            self.identifiers.sort_by_key(|identifier| {
+                Reverse((identifier.occurrences(), identifier.files()))
+            });
+        }
+        self
+    }
+
+    /// Answer user's `--keep-going` flag in json formats,
+    /// output the errors of skipped files in an "errors" field.
+    pub fn report_errors(mut self, errors: Vec<String>) -> Convertor {
+        self.errors = Some(errors);
+        self
+    }
+
+    /// ",\"errors\":[...]" with reported errors, or "".
+    fn errors_field(&self) -> String {
+        match &self.errors {
+            None => String::new(),
+            Some(errors) => {
+                format!(r#","errors":{}"#, Convertor::strings_to_json(errors))
+            }
+        }
+    }
+
+    /// "\<occurrences\> \<files\> " with stats, or "".
+    fn stats_prefix_of(&self, identifier: &Identifier) -> String {
+        if self.show_stats {
+            format!("{} {} ", identifier.occurrences(), identifier.files())
+        } else {
+            String::new()
+        }
+    }
+
+    fn append_stats_to_json(
+        &self,
+        object: &mut String,
+        identifier: &Identifier,
+    ) {
+        if self.show_stats {
+            let occurrences = identifier.occurrences().to_string();
+            Convertor::append_field_to_json(object, "count", &occurrences);
+            let files = identifier.files().to_string();
+            Convertor::append_field_to_json(object, "files", &files);
+        }
     }
 
     /// Normal output format, each line represent a captures in input text.
@@ -108,13 +614,41 @@ impl Convertor {
     /// \<origin string of capture1\> \<first target naming case\> \<second format\> ...
     /// \<origin string of capture2\> \<first target naming case\> \<second format\> ...
     /// ...
+    ///
+    /// With positions, each line represent an occurrence in input text:
+    /// \<path\>:\<line\>:\<column\>:\<origin string of capture1\> \<first target naming case\> ...
+    ///
+    /// Deduplicated with a normalized mode, the origin string becomes
+    /// all spellings of the identifier joined with commas.
+    ///
+    /// With stats, each line starts with how many times
+    /// and in how many files the capture occurs:
+    /// \<occurrences\> \<files\> \<origin string of capture1\> \<first target naming case\> ...
+    ///
+    /// Strings with spaces, like Title Case and Sentence case phrases,
+    /// are double-quoted (see `quote_phrase`):
+    /// page_size "Page Size" "Page size"
     pub fn into_lines(self) -> String {
         let mappers =
             self.select_mappers_base_on_options(&super::DIRECT_MAPPERS);
 
-        self.cases
-            .into_iter()
-            .map(|case| Convertor::one_word_to_line(&mappers, &case))
+        self.identifiers
+            .iter()
+            .flat_map(|identifier| {
+                let stats = self.stats_prefix_of(identifier);
+                let line = self.one_word_to_line(&mappers, identifier);
+                if self.show_positions && !identifier.positions.is_empty() {
+                    identifier
+                        .positions
+                        .iter()
+                        .map(|position| {
+                            format!("{}{}:{}", stats, position, line)
+                        })
+                        .collect()
+                } else {
+                    vec![stats + &line]
+                }
+            })
             .collect::<Vec<String>>()
             .join("\n")
     }
@@ -132,16 +666,21 @@ impl Convertor {
     }
 
     /// each word in input -> one line of result in output.
-    fn one_word_to_line(mappers: &[Formatter], case: &NamingCase) -> String {
+    fn one_word_to_line(
+        &self,
+        mappers: &[Formatter],
+        identifier: &Identifier,
+    ) -> String {
         // keep the origin string as the first word.
-        let mut line = case.to_string();
+        let mut line = quote_phrase(&identifier.origin());
         line.push(' ');
 
         // append target words behind.
+        let word = self.word_to_convert(identifier);
         line.push_str(
             &mappers
                 .iter()
-                .map(|f| (f.inner)(case))
+                .map(|f| quote_phrase(&(f.inner)(&word, &self.case_options)))
                 .collect::<Vec<String>>()
                 .join(" "),
         );
@@ -153,6 +692,27 @@ impl Convertor {
     ///
     /// Output looks like:
     /// {"result":[{"origin":\<capture1\>,\<first target format\>:\<converted string\>,...},...]}
+    ///
+    /// With positions, each element has an extra field:
+    /// "positions":[{"path":\<path or null\>,"line":1,"column":1,"offset":0},...]
+    ///
+    /// Stripped a hungarian notation prefix, each element has an extra field:
+    /// "prefix":\<prefix\>
+    ///
+    /// Stripped leading or trailing affixes, each element has an extra field:
+    /// "affix":{"leading":\<leading affix\>,"trailing":\<trailing affix\>}
+    ///
+    /// Captured by named placeholders, each element has an extra field:
+    /// "tags":[\<placeholder name\>,...]
+    ///
+    /// Deduplicated with a normalized mode, each element has an extra field:
+    /// "spellings":[\<origin string of capture1\>,\<same identifier in other spelling\>,...]
+    ///
+    /// With stats, each element has extra fields:
+    /// "count":\<occurrences\>,"files":\<files\>
+    ///
+    /// With reported errors, the root has an extra field:
+    /// "errors":[\<error message\>,...]
     pub fn into_json(self) -> String {
         let mappers = self.select_mappers_base_on_options(&super::JSON_MAPPERS);
 
@@ -160,25 +720,55 @@ impl Convertor {
 
         // string "{...},{...},..." for put into json array
         let json_array_fields = self
-            .cases
-            .into_iter()
-            .map(|case| Convertor::one_word_to_json(&mappers, &case))
+            .identifiers
+            .iter()
+            .map(|identifier| {
+                let mut line = self.one_word_to_json(&mappers, identifier);
+                Convertor::append_prefix_to_json(&mut line, &identifier.prefix);
+                Convertor::append_affixes_to_json(
+                    &mut line,
+                    &identifier.affixes,
+                );
+                Convertor::append_tags_to_json(&mut line, &identifier.tags);
+                Convertor::append_spellings_to_json(
+                    &mut line,
+                    &identifier.spellings,
+                );
+                self.append_stats_to_json(&mut line, identifier);
+                if self.show_positions {
+                    Convertor::append_field_to_json(
+                        &mut line,
+                        "positions",
+                        &Convertor::positions_to_json(&identifier.positions),
+                    );
+                }
+                line
+            })
             .collect::<Vec<String>>()
             .join(",");
 
         result.push_str(&json_array_fields);
-        result.push_str("]}");
+        result.push(']');
+        result.push_str(&self.errors_field());
+        result.push('}');
         // "{"result":[{...},{...},...]}"
         result
     }
 
-    fn one_word_to_json(mappers: &[Formatter], case: &NamingCase) -> String {
-        let mut line = r#"{"origin":""#.to_string() + &case.to_string() + "\",";
+    fn one_word_to_json(
+        &self,
+        mappers: &[Formatter],
+        identifier: &Identifier,
+    ) -> String {
+        let mut line = r#"{"origin":""#.to_string()
+            + &super::escape_json(&identifier.captured_word())
+            + "\",";
 
+        let word = self.word_to_convert(identifier);
         line.push_str(
             &mappers
                 .iter()
-                .map(|f| (f.inner)(case))
+                .map(|f| (f.inner)(&word, &self.case_options))
                 .collect::<Vec<String>>()
                 .join(","),
         );
@@ -188,6 +778,88 @@ impl Convertor {
         line
     }
 
+    /// Insert `"key":value` at the end of given json object.
+    fn append_field_to_json(object: &mut String, key: &str, value: &str) {
+        object.pop();
+        object.push_str(&format!(r#","{}":{}}}"#, key, value));
+    }
+
+    fn append_prefix_to_json(object: &mut String, prefix: &Option<String>) {
+        if let Some(prefix) = prefix {
+            // ""str""
+            let prefix = format!("\"{}\"", super::escape_json(prefix));
+            Convertor::append_field_to_json(object, "prefix", &prefix);
+        }
+    }
+
+    fn append_affixes_to_json(
+        object: &mut String,
+        (leading, trailing): &(String, String),
+    ) {
+        if leading.is_empty() && trailing.is_empty() {
+            return;
+        }
+        // "{"leading":"__","trailing":"__"}"
+        let affixes = format!(
+            r#"{{"leading":"{}","trailing":"{}"}}"#,
+            super::escape_json(leading),
+            super::escape_json(trailing)
+        );
+        Convertor::append_field_to_json(object, "affix", &affixes);
+    }
+
+    fn append_tags_to_json(object: &mut String, tags: &[String]) {
+        // "["name","type"]"
+        Convertor::append_strings_to_json(object, "tags", tags);
+    }
+
+    fn append_spellings_to_json(object: &mut String, spellings: &[String]) {
+        // "["pageSize","page_size"]"
+        Convertor::append_strings_to_json(object, "spellings", spellings);
+    }
+
+    /// Insert a string array field if given strings aren't empty.
+    fn append_strings_to_json(
+        object: &mut String,
+        key: &str,
+        strings: &[String],
+    ) {
+        if strings.is_empty() {
+            return;
+        }
+        let strings = Convertor::strings_to_json(strings);
+        Convertor::append_field_to_json(object, key, &strings);
+    }
+
+    fn strings_to_json(strings: &[String]) -> String {
+        let elements = strings
+            .iter()
+            .map(|string| format!("\"{}\"", super::escape_json(string)))
+            .collect::<Vec<String>>()
+            .join(",");
+        // "["a","b"]"
+        format!("[{}]", elements)
+    }
+
+    fn positions_to_json(positions: &[Position]) -> String {
+        let elements = positions
+            .iter()
+            .map(|position| {
+                let path = match &position.path {
+                    Some(path) => format!("\"{}\"", super::escape_json(path)),
+                    None => "null".to_string(),
+                };
+                format!(
+                    r#"{{"path":{},"line":{},"column":{},"offset":{}}}"#,
+                    path, position.line, position.column, position.offset
+                )
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        // "[{"path":"a.rs","line":1,"column":1,"offset":0},...]"
+        format!("[{}]", elements)
+    }
+
     /// Output in this format when user enters `--regex` option,
     /// each line represent a captures in input text.
     ///
@@ -195,30 +867,42 @@ impl Convertor {
     /// \<origin string of capture1\> \<target formats mixed OR regex (e.g. "aA|a_a|a-a")\>
     /// \<origin string of capture2\> \<target formats mixed OR regex\>
     /// ...
+    ///
+    /// Same as `into_lines`, each line may start with stats,
+    /// and the origin string and the regex are double-quoted with spaces.
     pub fn into_regex(self) -> String {
         let mappers =
             self.select_mappers_base_on_options(&super::DIRECT_MAPPERS);
 
-        self.cases
-            .into_iter()
-            .map(|case| Convertor::one_word_to_regex(&mappers, &case))
+        self.identifiers
+            .iter()
+            .map(|identifier| {
+                self.stats_prefix_of(identifier)
+                    + &self.one_word_to_regex(&mappers, identifier)
+            })
             .collect::<Vec<String>>()
             .join("\n")
     }
 
-    fn one_word_to_regex(mappers: &[Formatter], case: &NamingCase) -> String {
+    fn one_word_to_regex(
+        &self,
+        mappers: &[Formatter],
+        identifier: &Identifier,
+    ) -> String {
         // keep the origin string as the first word.
-        let mut line = case.to_string();
+        let mut line = quote_phrase(&identifier.origin());
         line.push(' ');
 
-        // join target formats into one regex string with "|"
-        line.push_str(
+        // join target formats into one regex string with "|",
+        // escape the dots of dot.case.
+        let word = self.word_to_convert(identifier);
+        line.push_str(&quote_phrase(
             &mappers
                 .iter()
-                .map(|f| (f.inner)(case))
+                .map(|f| escape_regex(&(f.inner)(&word, &self.case_options)))
                 .collect::<Vec<String>>()
                 .join("|"),
-        );
+        ));
         line
     }
 
@@ -227,6 +911,9 @@ impl Convertor {
     ///
     /// Output looks like:
     /// {"result":[{"origin":\<capture1\>,"regex":\<mixed regex string\>},{...},...]}
+    ///
+    /// Same as `into_json`, elements may have extra "prefix", "affix", "tags",
+    /// "spellings" and stats fields, and the root may have an extra "errors" field.
     pub fn into_regex_json(self) -> String {
         let mappers =
             self.select_mappers_base_on_options(&super::DIRECT_MAPPERS);
@@ -235,30 +922,54 @@ impl Convertor {
 
         // string "{...},{...},..." for put into json array
         let json_array_fields = self
-            .cases
-            .into_iter()
-            .map(|case| Convertor::one_word_to_regex_json(&mappers, &case))
+            .identifiers
+            .iter()
+            .map(|identifier| {
+                let mut line =
+                    self.one_word_to_regex_json(&mappers, identifier);
+                Convertor::append_prefix_to_json(&mut line, &identifier.prefix);
+                Convertor::append_affixes_to_json(
+                    &mut line,
+                    &identifier.affixes,
+                );
+                Convertor::append_tags_to_json(&mut line, &identifier.tags);
+                Convertor::append_spellings_to_json(
+                    &mut line,
+                    &identifier.spellings,
+                );
+                self.append_stats_to_json(&mut line, identifier);
+                line
+            })
             .collect::<Vec<String>>()
             .join(",");
 
         result.push_str(&json_array_fields);
-        result.push_str("]}");
+        result.push(']');
+        result.push_str(&self.errors_field());
+        result.push('}');
         // "{"result":[{...},{...},...]}"
         result
     }
 
     fn one_word_to_regex_json(
+        &self,
         mappers: &[Formatter],
-        case: &NamingCase,
+        identifier: &Identifier,
     ) -> String {
-        let mut line =
-            r#"{"origin":""#.to_string() + &case.to_string() + r#"","regex":""#;
+        let mut line = r#"{"origin":""#.to_string()
+            + &super::escape_json(&identifier.captured_word())
+            + r#"","regex":""#;
+        let word = self.word_to_convert(identifier);
 
-        // concat target formats into an OR regex
+        // concat target formats into an OR regex,
+        // escape the dots of dot.case, then the backslashes for json.
         line.push_str(
             &mappers
                 .iter()
-                .map(|f| (f.inner)(case))
+                .map(|f| {
+                    let converted = (f.inner)(&word, &self.case_options);
+                    super::escape_json(&escape_regex(&converted))
+                })
                 .collect::<Vec<String>>()
                 .join("|"),
         );
@@ -269,20 +980,73 @@ impl Convertor {
     }
 }
 
+/// Escape regex meta characters in a converted word,
+/// like the dots of dot.case.
+fn escape_regex(word: &str) -> String {
+    let mut escaped = String::with_capacity(word.len());
+    for c in word.chars() {
+        if r"\.+*?()|[]{}^$".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Double-quote a string with spaces in normal output formats,
+/// so that a phrase is still one word for `xargs` and shells,
+/// quotes and backslashes in it are escaped with backslashes.
+fn quote_phrase(word: &str) -> String {
+    if !word.contains(char::is_whitespace) {
+        return word.to_string();
+    }
+    let mut quoted = String::with_capacity(word.len() + 2);
+    quoted.push('"');
+    for c in word.chars() {
+        if c == '"' || c == '\\' {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
 #[cfg(test)]
 mod filter_tests {
     use naming_lib::NamingCase;
 
+    use crate::case::Format;
+    use crate::extraction::{Capture, Position};
     use crate::to_string_vec;
 
-    use super::Filter;
+    use super::{Dedup, Filter, Identifier};
 
     #[test]
-    fn find_hungarian_camel_conflict() {
-        assert!(Filter::has_hungarian_camel_conflict(&[
-            "c".to_string(),
-            "h".to_string()
-        ]));
+    fn resolve_negated_options() {
+        let resolve = |options: Vec<&str>| {
+            Filter::new(Some(to_string_vec(options)))
+                .map(|filter| filter.options)
+        };
+        assert_eq!(
+            resolve(vec![
+                "!k", "!T", "!K", "!d", "!r", "!f", "!F", "!A", "!t", "!e"
+            ]),
+            Ok(to_string_vec(vec!["S", "s", "c", "p"]))
+        );
+        assert_eq!(resolve(vec!["s", "k", "!k"]), Ok(to_string_vec(vec!["s"])));
+        assert_eq!(
+            resolve(vec!["h", "!k"]),
+            Ok(to_string_vec(vec![
+                "S", "s", "c", "p", "T", "K", "d", "r", "f", "F", "A", "t",
+                "e", "h"
+            ]))
+        );
+        assert_eq!(
+            resolve(vec!["S", "s", "k", "c", "!c", "h"]),
+            Ok(to_string_vec(vec!["S", "s", "k", "h"]))
+        );
+        assert!(resolve(vec!["s", "!s"]).is_err());
     }
 
     #[test]
@@ -304,18 +1068,269 @@ mod filter_tests {
         let mut expect = words.clone();
         // remove the invalid word at tail
         expect.pop();
-        let actual = Filter::filter_words_with_options(&filter, words);
+        let actual: Vec<String> = filter
+            .to_naming_cases_from(words)
+            .iter()
+            .map(|case| case.to_string())
+            .collect();
         assert_eq!(actual, expect);
     }
 
+    #[test]
+    fn filter_phrases() {
+        let filter = Filter::new(Some(to_string_vec(vec!["t", "e"]))).unwrap();
+        let words = to_string_vec(vec!["Page Size", "Page size", "page_size"]);
+        let actual: Vec<String> = filter
+            .to_naming_cases_from(words)
+            .iter()
+            .map(|case| case.to_string())
+            .collect();
+        assert_eq!(actual, to_string_vec(vec!["Page Size", "Page size"]));
+    }
+
+    #[test]
+    fn filter_words_with_bounds_and_numbers() {
+        let words = to_string_vec(vec![
+            "i",
+            "size",
+            "pageSize",
+            "max_page_size",
+            "42",
+            "0xFF",
+        ]);
+        let filter =
+            |filter: Filter| filter.to_naming_cases_from(words.clone());
+        let origins = |cases: Vec<NamingCase>| -> Vec<String> {
+            cases.iter().map(|case| case.to_string()).collect()
+        };
+
+        let actual =
+            filter(Filter::new(None).unwrap().length(Some(2), Some(8)));
+        assert_eq!(
+            origins(actual),
+            to_string_vec(vec!["size", "pageSize", "0xFF"])
+        );
+        let actual = filter(Filter::new(None).unwrap().words(Some(2), None));
+        assert_eq!(
+            origins(actual),
+            to_string_vec(vec!["pageSize", "max_page_size", "0xFF"])
+        );
+        let actual = filter(Filter::new(None).unwrap().skip_numbers(true));
+        assert_eq!(
+            origins(actual),
+            to_string_vec(vec!["i", "size", "pageSize", "max_page_size"])
+        );
+    }
+
+    #[test]
+    fn filter_words_with_regexes() {
+        let words = to_string_vec(vec!["userId", "test_user_id", "user_name"]);
+        let filter = Filter::new(None)
+            .unwrap()
+            .match_patterns(&["(?i)id$", "name"])
+            .unwrap()
+            .exclude_patterns(&["^test_"])
+            .unwrap();
+        let actual: Vec<String> = filter
+            .to_naming_cases_from(words)
+            .iter()
+            .map(|case| case.to_string())
+            .collect();
+        assert_eq!(actual, to_string_vec(vec!["userId", "user_name"]));
+        assert!(Filter::new(None).unwrap().match_patterns(&["("]).is_err());
+    }
+
     #[test]
     fn convert_words_as_hungarian_notation() {
         let options = vec!["h".to_string()];
-        let words: Vec<String> = vec!["intPageSize".to_string()];
+        let words =
+            to_string_vec(vec!["strPageSize", "userFirstName", "m_page_count"]);
+
+        let actual = Filter::new(Some(options.clone()))
+            .unwrap()
+            .to_naming_cases_from(words.clone());
+        assert_eq!(
+            actual,
+            vec![
+                NamingCase::Pascal("PageSize".to_string()),
+                NamingCase::Snake("page_count".to_string())
+            ]
+        );
+
+        let actual = Filter::new(Some(options))
+            .unwrap()
+            .hungarian_prefixes(Some(to_string_vec(vec!["user"])))
+            .to_naming_cases_from(words);
+        assert_eq!(actual, vec![NamingCase::Pascal("FirstName".to_string())]);
+    }
+
+    #[test]
+    fn detect_words_with_affixes() {
+        let captures = ["__init__", "_privateName", "$scope", "name_", "__"]
+            .iter()
+            .map(|word| Capture::from(word.to_string()))
+            .collect();
+
+        let actual = Filter::new(None).unwrap().to_identifiers_from(captures);
+        let affixes = |leading: &str, trailing: &str| {
+            (leading.to_string(), trailing.to_string())
+        };
+        assert_eq!(actual.len(), 4);
+        assert_eq!(actual[0].case.to_string(), "init");
+        assert_eq!(actual[0].affixes, affixes("__", "__"));
+        assert_eq!(
+            actual[1].case,
+            NamingCase::Camel("privateName".to_string())
+        );
+        assert_eq!(actual[1].affixes, affixes("_", ""));
+        assert_eq!(actual[1].word(), "_privateName");
+        assert_eq!(actual[2].affixes, affixes("$", ""));
+        assert_eq!(actual[3].affixes, affixes("", "_"));
+    }
+
+    #[test]
+    fn hungarian_notation_coexists_with_camel_case() {
+        let options = to_string_vec(vec!["c", "h"]);
+        let captures = vec![
+            Capture::from("strPageSize".to_string()),
+            Capture::from("userName".to_string()),
+        ];
 
         let actual =
-            Filter::new(Some(options)).unwrap().to_naming_cases_from(words);
-        assert_eq!(actual, vec![NamingCase::Pascal("PageSize".to_string())]);
+            Filter::new(Some(options)).unwrap().to_identifiers_from(captures);
+        assert_eq!(actual[0].case, NamingCase::Pascal("PageSize".to_string()));
+        assert_eq!(actual[0].prefix, Some("str".to_string()));
+        assert_eq!(actual[1].case, NamingCase::Camel("userName".to_string()));
+        assert_eq!(actual[1].prefix, None);
+    }
+
+    #[test]
+    fn hungarian_notation_with_affixes() {
+        let options = to_string_vec(vec!["h"]);
+        let captures = vec![Capture::from("_strName".to_string())];
+
+        let actual =
+            Filter::new(Some(options)).unwrap().to_identifiers_from(captures);
+        assert_eq!(actual[0].case.to_string(), "Name");
+        assert_eq!(actual[0].prefix, Some("str".to_string()));
+        assert_eq!(actual[0].word(), "_Name");
+        assert_eq!(actual[0].origin(), "_strName");
+    }
+
+    #[test]
+    fn keep_positions_when_converting_captures() {
+        let position = Position { path: None, line: 1, column: 1, offset: 0 };
+        let captures = vec![
+            Capture {
+                word: "aA".to_string(),
+                positions: vec![position.clone()],
+                tags: vec!["name".to_string()],
+            },
+            Capture::from("-invalid_".to_string()),
+        ];
+
+        let actual = Filter::new(None).unwrap().to_identifiers_from(captures);
+        assert_eq!(
+            actual,
+            vec![Identifier {
+                case: NamingCase::Camel("aA".to_string()),
+                format: Some(Format::Camel),
+                positions: vec![position],
+                tags: vec!["name".to_string()],
+                spellings: Vec::new(),
+                prefix: None,
+                affixes: (String::new(), String::new()),
+            }]
+        );
+    }
+
+    #[test]
+    fn dedup_identifiers_with_modes() {
+        let position =
+            |offset| Position { path: None, line: 1, column: 1, offset };
+        let captures: Vec<Capture> =
+            vec!["pageSize", "PageSize", "page_size", "maxSize"]
+                .into_iter()
+                .enumerate()
+                .map(|(offset, word)| Capture {
+                    positions: vec![position(offset)],
+                    ..Capture::from(word.to_string())
+                })
+                .collect();
+        let dedup = |mode| {
+            Filter::new(None)
+                .unwrap()
+                .dedup(mode)
+                .to_identifiers_from(captures.clone())
+                .into_iter()
+                .map(|identifier| {
+                    (identifier.origin(), identifier.occurrences())
+                })
+                .collect::<Vec<(String, usize)>>()
+        };
+
+        let origins = |pairs: Vec<(&str, usize)>| {
+            pairs
+                .into_iter()
+                .map(|(origin, count)| (origin.to_string(), count))
+                .collect::<Vec<(String, usize)>>()
+        };
+        assert_eq!(
+            dedup(Dedup::Exact),
+            origins(vec![
+                ("pageSize", 1),
+                ("PageSize", 1),
+                ("page_size", 1),
+                ("maxSize", 1)
+            ])
+        );
+        assert_eq!(
+            dedup(Dedup::IgnoreCase),
+            origins(vec![
+                ("pageSize,PageSize", 2),
+                ("page_size", 1),
+                ("maxSize", 1)
+            ])
+        );
+        assert_eq!(
+            dedup(Dedup::Words),
+            origins(vec![("pageSize,PageSize,page_size", 3), ("maxSize", 1)])
+        );
+    }
+
+    #[test]
+    fn sort_grouped_positions_by_files() {
+        let position = |path: &str, offset| Position {
+            path: Some(path.to_string()),
+            line: 1,
+            column: offset + 1,
+            offset,
+        };
+        // "b.txt" is passed twice, both of its occurrences are kept.
+        let captures = vec![
+            Capture {
+                positions: vec![position("b.txt", 5), position("a.txt", 3)],
+                ..Capture::from("pageSize".to_string())
+            },
+            Capture {
+                positions: vec![position("a.txt", 1), position("b.txt", 5)],
+                ..Capture::from("page_size".to_string())
+            },
+        ];
+
+        let actual = Filter::new(None)
+            .unwrap()
+            .dedup(Dedup::Words)
+            .to_identifiers_from(captures);
+        assert_eq!(
+            actual[0].positions,
+            vec![
+                position("b.txt", 5),
+                position("b.txt", 5),
+                position("a.txt", 1),
+                position("a.txt", 3)
+            ]
+        );
     }
 
     #[test]
@@ -352,9 +1367,11 @@ mod filter_tests {
 mod convertor_tests {
     use naming_lib as naming;
 
+    use crate::case;
+    use crate::extraction::Position;
     use crate::to_string_vec;
 
-    use super::Convertor;
+    use super::{quote_phrase, Convertor, Identifier};
 
     #[test]
     fn output_to_lines() {
@@ -367,8 +1384,7 @@ mod convertor_tests {
             "camelCase",
             "PascalCase",
         ];
-        let cases =
-            words.into_iter().map(|word| naming::which_case(word)).collect();
+        let cases = words.into_iter().map(naming::which_case).collect();
 
         let actual = Convertor::new(Some(options), cases).into_lines();
 
@@ -385,10 +1401,7 @@ PascalCase PASCAL_CASE pascal_case pascal-case pascalCase PascalCase";
     #[test]
     fn output_bases_on_options_order() {
         let options = to_string_vec(vec!["p", "c", "s", "k", "S"]);
-        let cases = vec!["a_a"]
-            .into_iter()
-            .map(|word| naming::which_case(word))
-            .collect();
+        let cases = vec!["a_a"].into_iter().map(naming::which_case).collect();
 
         let actual = Convertor::new(Some(options), cases).into_lines();
         assert_eq!(actual.as_str(), "a_a AA aA a_a a-a A_A");
@@ -398,8 +1411,7 @@ PascalCase PASCAL_CASE pascal_case pascal-case pascalCase PascalCase";
     fn output_to_json() {
         let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
         let words = vec!["snake_case", "kebab-case"];
-        let cases =
-            words.into_iter().map(|word| naming::which_case(word)).collect();
+        let cases = words.into_iter().map(naming::which_case).collect();
 
         let actual = Convertor::new(Some(options), cases).into_json();
 
@@ -418,8 +1430,7 @@ PascalCase PASCAL_CASE pascal_case pascal-case pascalCase PascalCase";
         let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
 
         let words = vec!["SCREAMING_SNAKE", "snake_case"];
-        let cases =
-            words.into_iter().map(|word| naming::which_case(word)).collect();
+        let cases = words.into_iter().map(naming::which_case).collect();
 
         let actual = Convertor::new(Some(options), cases).into_regex();
 
@@ -434,8 +1445,7 @@ snake_case SNAKE_CASE|snake_case|snake-case|snakeCase|SnakeCase";
     fn output_to_regex_json() {
         let options = to_string_vec(vec!["S", "s", "k", "c", "p"]);
         let words = vec!["SCREAMING_SNAKE", "snake_case"];
-        let cases =
-            words.into_iter().map(|word| naming::which_case(word)).collect();
+        let cases = words.into_iter().map(naming::which_case).collect();
 
         let actual = Convertor::new(Some(options), cases).into_regex_json();
 
@@ -448,4 +1458,319 @@ snake_case SNAKE_CASE|snake_case|snake-case|snakeCase|SnakeCase";
 
         assert_eq!(actual.as_str(), expect);
     }
+
+    #[test]
+    fn escape_captured_words_in_json() {
+        let options = to_string_vec(vec!["s"]);
+        let word = r#"a"b\c"#;
+        let identifiers = || {
+            vec![Identifier::from(naming::NamingCase::Invalid(
+                word.to_string(),
+            ))]
+        };
+        let output = |into: fn(Convertor) -> String| {
+            let json = into(convertor(&options, identifiers()));
+            serde_json::from_str::<serde_json::Value>(&json).unwrap()
+        };
+
+        let actual = output(Convertor::into_json);
+        assert_eq!(actual["result"][0]["origin"], word);
+        assert!(actual["result"][0]["snake"].is_string());
+        let actual = output(Convertor::into_regex_json);
+        assert_eq!(actual["result"][0]["origin"], word);
+        assert!(actual["result"][0]["regex"].is_string());
+    }
+
+    fn convertor(
+        options: &[String],
+        identifiers: Vec<Identifier>,
+    ) -> Convertor {
+        Convertor::from_identifiers(Some(options.to_vec()), identifiers)
+    }
+
+    fn identifier_with_positions(word: &str) -> Identifier {
+        let position = |path: Option<&str>, line, offset| Position {
+            path: path.map(|path| path.to_string()),
+            line,
+            column: 1,
+            offset,
+        };
+        Identifier {
+            case: naming::which_case(word),
+            format: case::which_format(word),
+            positions: vec![
+                position(Some("a\\b.rs"), 1, 0),
+                position(None, 2, 4),
+            ],
+            tags: Vec::new(),
+            spellings: Vec::new(),
+            prefix: None,
+            affixes: (String::new(), String::new()),
+        }
+    }
+
+    #[test]
+    fn output_to_lines_with_positions() {
+        let options = to_string_vec(vec!["s"]);
+        let identifiers = vec![identifier_with_positions("aA")];
+
+        let actual = Convertor::from_identifiers(Some(options), identifiers)
+            .show_positions(true)
+            .into_lines();
+        assert_eq!(actual.as_str(), "a\\b.rs:1:1:aA a_a\n2:1:aA a_a");
+    }
+
+    #[test]
+    fn output_to_json_with_positions() {
+        let options = to_string_vec(vec!["s"]);
+        let identifiers = vec![identifier_with_positions("aA")];
+
+        let actual = Convertor::from_identifiers(Some(options), identifiers)
+            .show_positions(true)
+            .into_json();
+
+        let expect = concat!(
+            r#"{"result":[{"origin":"aA","snake":"a_a","positions":["#,
+            r#"{"path":"a\\b.rs","line":1,"column":1,"offset":0},"#,
+            r#"{"path":null,"line":2,"column":1,"offset":4}]}]}"#
+        );
+        assert_eq!(actual.as_str(), expect);
+    }
+
+    #[test]
+    fn output_tags_in_json() {
+        let options = to_string_vec(vec!["s"]);
+        let identifiers = || {
+            vec![Identifier {
+                tags: to_string_vec(vec!["name", "type"]),
+                ..Identifier::from(naming::which_case("aA"))
+            }]
+        };
+
+        let actual = convertor(&options, identifiers()).into_json();
+        assert_eq!(
+            actual.as_str(),
+            r#"{"result":[{"origin":"aA","snake":"a_a","tags":["name","type"]}]}"#
+        );
+
+        let actual = convertor(&options, identifiers()).into_regex_json();
+        assert_eq!(
+            actual.as_str(),
+            r#"{"result":[{"origin":"aA","regex":"a_a","tags":["name","type"]}]}"#
+        );
+    }
+
+    #[test]
+    fn output_additional_cases() {
+        let options = to_string_vec(vec!["T", "K", "d", "r", "f", "F", "A"]);
+        let identifiers =
+            || vec![Identifier::from(naming::which_case("pageSize"))];
+        assert_eq!(
+            convertor(&options, identifiers()).into_lines().as_str(),
+            "pageSize Page-Size PAGE-SIZE page.size page/size pagesize PAGESIZE Page_Size"
+        );
+        assert_eq!(
+            convertor(&options, identifiers()).into_json().as_str(),
+            concat!(
+                r#"{"result":[{"origin":"pageSize","train":"Page-Size","cobol":"PAGE-SIZE","#,
+                r#""dot":"page.size","path":"page/size","flat":"pagesize","#,
+                r#""upper_flat":"PAGESIZE","ada":"Page_Size"}]}"#
+            )
+        );
+        assert_eq!(
+            convertor(&options, identifiers()).into_regex().as_str(),
+            r"pageSize Page-Size|PAGE-SIZE|page\.size|page/size|pagesize|PAGESIZE|Page_Size"
+        );
+        assert!(convertor(&options, identifiers())
+            .into_regex_json()
+            .contains(r#""regex":"Page-Size|PAGE-SIZE|page\\.size|"#));
+    }
+
+    #[test]
+    fn output_phrases() {
+        let options = to_string_vec(vec!["t", "e"]);
+        let identifiers =
+            || vec![Identifier::from(naming::which_case("page_size"))];
+        assert_eq!(
+            convertor(&options, identifiers()).into_lines().as_str(),
+            r#"page_size "Page Size" "Page size""#
+        );
+        assert_eq!(
+            convertor(&options, identifiers()).into_regex().as_str(),
+            r#"page_size "Page Size|Page size""#
+        );
+        assert_eq!(
+            convertor(&options, identifiers()).into_json().as_str(),
+            r#"{"result":[{"origin":"page_size","title":"Page Size","sentence":"Page size"}]}"#
+        );
+    }
+
+    #[test]
+    fn quote_phrases_only() {
+        assert_eq!(quote_phrase("page_size"), "page_size");
+        assert_eq!(quote_phrase("Page Size"), r#""Page Size""#);
+        assert_eq!(quote_phrase(r#"say "hi" \ bye"#), r#""say \"hi\" \\ bye""#);
+    }
+
+    #[test]
+    fn output_with_acronyms() {
+        let options = to_string_vec(vec!["s", "c", "p"]);
+        let identifiers = || {
+            vec![
+                Identifier::from(naming::which_case("userID")),
+                Identifier::from(naming::which_case("xml_http_request")),
+            ]
+        };
+        assert_eq!(
+            convertor(&options, identifiers()).into_lines().as_str(),
+            "userID user_id userId UserId\n\
+            xml_http_request xml_http_request xmlHttpRequest XmlHttpRequest"
+        );
+        assert_eq!(
+            convertor(&options, identifiers())
+                .acronyms(to_string_vec(vec!["ID", "xml"]))
+                .into_lines()
+                .as_str(),
+            "userID user_id userID UserID\n\
+            xml_http_request xml_http_request xmlHttpRequest XMLHttpRequest"
+        );
+    }
+
+    #[test]
+    fn output_with_affixes() {
+        let options = to_string_vec(vec!["S", "c"]);
+        let identifiers = || {
+            vec![Identifier {
+                affixes: ("_".to_string(), "_".to_string()),
+                ..Identifier::from(naming::which_case("page_size"))
+            }]
+        };
+        assert_eq!(
+            convertor(&options, identifiers()).into_lines().as_str(),
+            "_page_size_ _PAGE_SIZE_ _pageSize_"
+        );
+        assert_eq!(
+            convertor(&options, identifiers())
+                .strip_affixes(true)
+                .into_lines()
+                .as_str(),
+            "_page_size_ PAGE_SIZE pageSize"
+        );
+        assert_eq!(
+            convertor(&options, identifiers()).into_json().as_str(),
+            concat!(
+                r#"{"result":[{"origin":"_page_size_","screaming_snake":"_PAGE_SIZE_","#,
+                r#""camel":"_pageSize_","affix":{"leading":"_","trailing":"_"}}]}"#
+            )
+        );
+        assert_eq!(
+            convertor(&options, identifiers())
+                .strip_affixes(true)
+                .into_regex_json()
+                .as_str(),
+            concat!(
+                r#"{"result":[{"origin":"_page_size_","regex":"PAGE_SIZE|pageSize","#,
+                r#""affix":{"leading":"_","trailing":"_"}}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn output_prefix_in_json() {
+        let options = to_string_vec(vec!["s"]);
+        let identifiers = || {
+            vec![Identifier {
+                prefix: Some("str".to_string()),
+                ..Identifier::from(naming::which_case("Name"))
+            }]
+        };
+        assert_eq!(
+            convertor(&options, identifiers()).into_json().as_str(),
+            r#"{"result":[{"origin":"strName","snake":"name","prefix":"str"}]}"#
+        );
+        assert_eq!(
+            convertor(&options, identifiers()).into_regex_json().as_str(),
+            r#"{"result":[{"origin":"strName","regex":"name","prefix":"str"}]}"#
+        );
+    }
+
+    #[test]
+    fn output_spellings() {
+        let options = to_string_vec(vec!["s"]);
+        let identifiers = || {
+            vec![Identifier {
+                spellings: to_string_vec(vec!["aA", "a_a"]),
+                ..Identifier::from(naming::which_case("aA"))
+            }]
+        };
+        assert_eq!(
+            convertor(&options, identifiers()).into_lines().as_str(),
+            "aA,a_a a_a"
+        );
+        assert_eq!(
+            convertor(&options, identifiers()).into_regex().as_str(),
+            "aA,a_a a_a"
+        );
+        assert_eq!(
+            convertor(&options, identifiers()).into_json().as_str(),
+            r#"{"result":[{"origin":"aA","snake":"a_a","spellings":["aA","a_a"]}]}"#
+        );
+    }
+
+    #[test]
+    fn count_occurrences_and_files() {
+        let identifier = identifier_with_positions("aA");
+        assert_eq!(identifier.occurrences(), 2);
+        assert_eq!(identifier.files(), 2);
+        assert_eq!(Identifier::from(naming::which_case("aA")).files(), 0);
+    }
+
+    #[test]
+    fn output_with_stats_sorted_by_frequency() {
+        let options = to_string_vec(vec!["s"]);
+        let identifiers = || {
+            vec![
+                Identifier::from(naming::which_case("bB")),
+                identifier_with_positions("aA"),
+            ]
+        };
+        let sorted = || {
+            convertor(&options, identifiers())
+                .show_stats(true)
+                .sort_by_frequency(true)
+        };
+
+        assert_eq!(sorted().into_lines().as_str(), "2 2 aA a_a\n0 0 bB b_b");
+        assert_eq!(sorted().into_regex().as_str(), "2 2 aA a_a\n0 0 bB b_b");
+        assert_eq!(
+            sorted().into_json().as_str(),
+            concat!(
+                r#"{"result":[{"origin":"aA","snake":"a_a","count":2,"files":2},"#,
+                r#"{"origin":"bB","snake":"b_b","count":0,"files":0}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn output_errors_in_json() {
+        let options = to_string_vec(vec!["s"]);
+        let identifiers = || vec![Identifier::from(naming::which_case("aA"))];
+        let errors = to_string_vec(vec!["naming: \"b.txt\": denied"]);
+
+        let actual = convertor(&options, identifiers())
+            .report_errors(errors.clone())
+            .into_json();
+        assert_eq!(
+            actual.as_str(),
+            r#"{"result":[{"origin":"aA","snake":"a_a"}],"errors":["naming: \"b.txt\": denied"]}"#
+        );
+
+        let actual = convertor(&options, identifiers())
+            .report_errors(Vec::new())
+            .into_regex_json();
+        assert_eq!(
+            actual.as_str(),
+            r#"{"result":[{"origin":"aA","regex":"a_a"}],"errors":[]}"#
+        );
+    }
 }
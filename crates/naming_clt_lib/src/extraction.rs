@@ -1,59 +1,487 @@
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::fs;
-use std::io::{self, BufRead};
+use std::io::{self, Read};
+use std::ops::Range;
 
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
 use fancy_regex::Regex;
 
+use crate::language::Language;
+use crate::lexer::Lexer;
+use crate::marker::Markers;
+use crate::pragma::Pragmas;
+
+/// One piece of input text, with the path of the file it comes from
+/// (None for stdin), and byte ranges of the text that should be ignored
+/// (outside the logical start and regions, see `Markers`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Source {
+    pub path: Option<String>,
+    pub text: String,
+    pub ignored: Vec<Range<usize>>,
+}
+
+impl Source {
+    /// Language of this source, guessed from the extension of its path.
+    pub fn language(&self) -> Option<Language> {
+        self.path.as_deref().and_then(Language::from_path)
+    }
+
+    /// Append the next piece of the same input.
+    fn append(&mut self, piece: Source) {
+        let shift = self.text.len();
+        for range in piece.ignored {
+            let range = range.start + shift..range.end + shift;
+            match self.ignored.last_mut() {
+                // join the range split by pieces.
+                Some(last) if last.end == shift && range.start == shift => {
+                    last.end = range.end
+                }
+                _ => self.ignored.push(range),
+            }
+        }
+        self.text.push_str(&piece.text);
+    }
+}
+
+impl From<String> for Source {
+    fn from(text: String) -> Self {
+        Source { path: None, text, ignored: Vec::new() }
+    }
+}
+
+/// Where a captured word occurs in input.
+/// Line and column (counted in characters) start from 1,
+/// byte offset starts from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Position {
+    pub path: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+impl Position {
+    fn new(source: &Source, line_starts: &[usize], offset: usize) -> Position {
+        // index of the last line start that isn't behind the offset.
+        let line = line_starts.partition_point(|start| *start <= offset);
+        let column = source.text[line_starts[line - 1]..offset].chars().count();
+        Position { path: source.path.clone(), line, column: column + 1, offset }
+    }
+}
+
+impl fmt::Display for Position {
+    /// Looks like "path:line:column", or "line:column" for stdin.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(path) = &self.path {
+            write!(f, "{}:", path)?;
+        }
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A captured word, along with the positions of all its occurrences,
+/// and the names of the placeholders (like "name" in "{name}") that captured it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    pub word: String,
+    pub positions: Vec<Position>,
+    pub tags: Vec<String>,
+}
+
+impl From<String> for Capture {
+    fn from(word: String) -> Self {
+        Capture { word, positions: Vec::new(), tags: Vec::new() }
+    }
+}
+
 /// Convert Vec<String> into a slice of &str in Rust:
 /// https://stackoverflow.com/a/41180422/11397457
 pub fn read_from_files<T: AsRef<str>>(
     files: &[T],
     logical_eof: Option<&str>,
 ) -> Result<Vec<String>, String> {
-    let mut text = Vec::new();
-    for file in files {
-        text.push(read_file(file.as_ref(), logical_eof)?);
+    let sources = read_sources_from_files(files, logical_eof)?;
+    Ok(sources.into_iter().map(|source| source.text).collect())
+}
+
+/// Same as `read_from_files`, but keep the path along with each file's text,
+/// so that `Captor` can choose locators by the file's language.
+/// Binary files are skipped, see `Reader`.
+pub fn read_sources_from_files<T: AsRef<str>>(
+    files: &[T],
+    logical_eof: Option<&str>,
+) -> Result<Vec<Source>, String> {
+    Reader::new(logical_eof).read_files(files)
+}
+
+/// Answer user's `--encoding`, `--lossy` and `--keep-going` options,
+/// read files (and stdin) as bytes, skip binary ones and decode the rest,
+/// then apply markers on each of them.
+pub struct Reader {
+    markers: Markers,
+    // None for UTF-8, the default.
+    encoding: Option<&'static Encoding>,
+    lossy: bool,
+    keep_going: bool,
+    warnings: Vec<String>,
+    errors: Vec<String>,
+}
+
+impl Reader {
+    /// Take a literal logical EOF, see `Markers::eof`.
+    pub fn new(logical_eof: Option<&str>) -> Reader {
+        let markers = match logical_eof {
+            None => Markers::new(false),
+            Some(eof) => Markers::new(false)
+                .eof(eof)
+                .expect("literal markers should be valid"),
+        };
+        Reader {
+            markers,
+            encoding: None,
+            lossy: false,
+            keep_going: false,
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Replace the logical EOF passed to `new` with given markers.
+    pub fn markers(mut self, markers: Markers) -> Reader {
+        self.markers = markers;
+        self
+    }
+
+    /// Decode input without BOM in the encoding of given label,
+    /// like "latin1", "shift_jis" or "utf-16le", check labels in:
+    /// https://encoding.spec.whatwg.org/#names-and-labels
+    pub fn encoding(mut self, label: &str) -> Result<Reader, String> {
+        match Encoding::for_label(label.as_bytes()) {
+            Some(encoding) => {
+                self.encoding = Some(encoding);
+                Ok(self)
+            }
+            None => {
+                Err(format!("naming: encoding `{}`: unknown encoding.", label))
+            }
+        }
+    }
+
+    /// Replace malformed byte sequences with "\u{FFFD}",
+    /// instead of returning an Err.
+    pub fn lossy(mut self, lossy: bool) -> Reader {
+        self.lossy = lossy;
+        self
+    }
+
+    /// Skip the files that can't be read or decoded, instead of returning
+    /// an Err on the first one of them, errors are kept in `errors`.
+    pub fn keep_going(mut self, keep_going: bool) -> Reader {
+        self.keep_going = keep_going;
+        self
+    }
+
+    /// Messages of the files skipped in keep going mode.
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Messages of the skipped binary files.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    pub fn read_files<T: AsRef<str>>(
+        &mut self,
+        files: &[T],
+    ) -> Result<Vec<Source>, String> {
+        let mut sources = Vec::new();
+        for file in files {
+            match self.read_file(file.as_ref()) {
+                Ok(Some(source)) => sources.push(source),
+                Ok(None) => {}
+                Err(msg) if self.keep_going => self.errors.push(msg),
+                Err(msg) => return Err(msg),
+            }
+        }
+        Ok(sources)
+    }
+
+    /// Return None if it's a binary file.
+    pub fn read_file(&mut self, file: &str) -> Result<Option<Source>, String> {
+        let input = fs::File::open(file)
+            .map_err(|msg| format!("naming: {}: {}", file, msg))?;
+        let source = self.read_from(file, input)?;
+        Ok(source
+            .map(|source| Source { path: Some(file.to_string()), ..source }))
+    }
+
+    /// Binary stdin is read as an empty text.
+    pub fn read_stdin(&mut self) -> Result<Source, String> {
+        let source = self.read_from("stdin", io::stdin().lock())?;
+        Ok(source.unwrap_or_else(|| Source::from(String::new())))
+    }
+
+    /// Same as `read_files`, but each file is passed to `capturing`
+    /// piece by piece while it's read, instead of being kept in memory
+    /// as a whole. The captures of a file that fails in the middle
+    /// of reading are discarded.
+    pub fn capture_files<T: AsRef<str>>(
+        &mut self,
+        files: &[T],
+        capturing: &mut Capturing,
+    ) -> Result<(), String> {
+        for file in files {
+            let file = file.as_ref();
+            let read = fs::File::open(file)
+                .map_err(|msg| format!("naming: {}: {}", file, msg))
+                .and_then(|input| {
+                    self.read_pieces_from(file, input, &mut |piece| {
+                        capturing.feed(Source {
+                            path: Some(file.to_string()),
+                            ..piece
+                        })
+                    })
+                });
+            match read {
+                Ok(_) => capturing.end_source(),
+                Err(msg) => {
+                    capturing.discard_source();
+                    if !self.keep_going {
+                        return Err(msg);
+                    }
+                    self.errors.push(msg);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Same as `read_stdin`, but stdin is passed to `capturing`
+    /// piece by piece while it's read, see `capture_files`.
+    pub fn capture_stdin(
+        &mut self,
+        capturing: &mut Capturing,
+    ) -> Result<(), String> {
+        let input = io::stdin().lock();
+        self.read_pieces_from("stdin", input, &mut |piece| {
+            capturing.feed(piece)
+        })?;
+        capturing.end_source();
+        Ok(())
+    }
+
+    /// Read the whole input as one source, return None if it's binary.
+    fn read_from<R: Read>(
+        &mut self,
+        name: &str,
+        input: R,
+    ) -> Result<Option<Source>, String> {
+        let mut source = Source::from(String::new());
+        let is_text = self
+            .read_pieces_from(name, input, &mut |piece| source.append(piece))?;
+        Ok(if is_text { Some(source) } else { None })
+    }
+
+    /// Read and decode input chunk by chunk, apply markers on it,
+    /// and pass the text to `each` piece by piece, with the ignored ranges
+    /// in each piece. Only a bounded tail of the text is kept for markers
+    /// to look around, and the reading stops as soon as a logical EOF
+    /// occurs, the rest of input is never read.
+    /// Return false if it's a binary input, which is skipped.
+    fn read_pieces_from<R: Read>(
+        &mut self,
+        name: &str,
+        mut input: R,
+        each: &mut dyn FnMut(Source),
+    ) -> Result<bool, String> {
+        let io_error = |msg: io::Error| format!("naming: {}: {}", name, msg);
+
+        // sniff the encoding and binary from the head of input.
+        let mut head = Vec::new();
+        (&mut input)
+            .take(BINARY_SNIFFING_LENGTH)
+            .read_to_end(&mut head)
+            .map_err(io_error)?;
+        // a BOM always wins, then the user's encoding.
+        let (encoding, bom_length) = match Encoding::for_bom(&head) {
+            Some((encoding, length)) => (encoding, length),
+            None => (self.encoding.unwrap_or(UTF_8), 0),
+        };
+        // UTF-16 text is full of NUL bytes.
+        let is_utf_16 = encoding == UTF_16LE || encoding == UTF_16BE;
+        if !is_utf_16 && is_binary(&head) {
+            self.warnings
+                .push(format!("naming: {}: binary file, skipped.", name));
+            return Ok(false);
+        }
+
+        let mut decoder = encoding.new_decoder_without_bom_handling();
+        let mut scan = self.markers.scan();
+        let lookahead = self.markers.lookahead().unwrap_or(CONTEXT_LENGTH);
+        // decoded text starts at `base` of the whole input, the part
+        // before `settled` has been passed, and is kept as the context
+        // for markers to look behind.
+        let mut text = String::new();
+        let mut base = 0;
+        let mut settled = 0;
+        let mut chunk = head[bom_length..].to_vec();
+        let mut buffer = vec![0; CHUNK_LENGTH];
+        loop {
+            let last = chunk.is_empty();
+            if let Some(length) = decoder.max_utf8_buffer_length(chunk.len()) {
+                text.reserve(length);
+            }
+            // the reserved space is enough for decoding the whole chunk.
+            let (_, _, had_errors) =
+                decoder.decode_to_string(&chunk, &mut text, last);
+            if had_errors && !self.lossy {
+                return Err(format!(
+                    "naming: {}: stream did not contain valid {}, \
+                    pass `--encoding` to set its encoding, \
+                    or `--lossy` to replace malformed bytes.",
+                    name,
+                    encoding.name()
+                ));
+            }
+
+            // leave enough text behind the settled part
+            // for markers to look ahead.
+            let end = if last {
+                base + text.len()
+            } else {
+                let end = text.len().saturating_sub(lookahead);
+                base + floor_char_boundary(&text, end)
+            };
+            if end > settled || last {
+                let eof = scan.advance(&text, base, end);
+                let end = eof.unwrap_or(end);
+                let ignored = scan.ignored(settled, end);
+                let piece = text[settled - base..end - base].to_string();
+                if !piece.is_empty() {
+                    each(Source { path: None, text: piece, ignored });
+                }
+                if eof.is_some() {
+                    break;
+                }
+                let context = floor_char_boundary(
+                    &text,
+                    (end - base).saturating_sub(CONTEXT_LENGTH),
+                );
+                text.drain(..context);
+                base += context;
+                settled = end;
+            }
+            if last {
+                break;
+            }
+            chunk = read_chunk(&mut input, &mut buffer).map_err(io_error)?;
+        }
+        Ok(true)
     }
-    Ok(text)
 }
 
-fn read_file(file: &str, eof: Option<&str>) -> Result<String, String> {
-    match fs::read_to_string(file) {
-        Ok(text) => Ok(trim_text_with_logical_eof(eof, text)),
-        Err(msg) => Err(format!("naming: {}: {}", file, msg)),
+// lengths are much shorter in unit tests, so that small inputs
+// go through the same boundaries as large ones.
+const BINARY_SNIFFING_LENGTH: u64 = 8192;
+#[cfg(not(test))]
+const CHUNK_LENGTH: usize = 64 * 1024;
+#[cfg(test)]
+const CHUNK_LENGTH: usize = 256;
+/// How far markers and locators can look behind and ahead of an occurrence
+/// when input is processed piece by piece.
+#[cfg(not(test))]
+const CONTEXT_LENGTH: usize = 64 * 1024;
+#[cfg(test)]
+const CONTEXT_LENGTH: usize = 1024;
+/// `Capturing` matches locators on pieces of at least this length at a time.
+#[cfg(not(test))]
+const PIECE_LENGTH: usize = 1024 * 1024;
+#[cfg(test)]
+const PIECE_LENGTH: usize = 4096;
+
+/// The nearest char boundary at or before `index`.
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
     }
+    index
 }
 
-fn trim_text_with_logical_eof(eof: Option<&str>, text: String) -> String {
-    match eof {
-        None => text,
-        Some(eof) => {
-            let position = text.find(eof);
-            text[..position.unwrap_or_else(|| text.len())].to_string()
+/// Read the next chunk of input, an empty one means the end of input.
+fn read_chunk<R: Read>(
+    input: &mut R,
+    buffer: &mut [u8],
+) -> io::Result<Vec<u8>> {
+    loop {
+        match input.read(buffer) {
+            Ok(length) => return Ok(buffer[..length].to_vec()),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
         }
     }
 }
 
+/// Like git and grep, a NUL byte in the first 8KB means a binary file.
+fn is_binary(head: &[u8]) -> bool {
+    head.contains(&0)
+}
+
 pub fn read_from_std_in(eof: Option<&str>) -> Result<String, String> {
     read_from_input(io::stdin().lock(), eof)
 }
 
 /// How to test stdin https://stackoverflow.com/a/28370712/11397457
-fn read_from_input<R>(mut input: R, eof: Option<&str>) -> Result<String, String>
-where
-    R: BufRead,
-{
-    let mut buffer = String::new();
-    match input.read_to_string(&mut buffer) {
-        Ok(_) => Ok(trim_text_with_logical_eof(eof, buffer)),
-        Err(msg) => Err(format!("naming: stdin: {}", msg)),
-    }
+fn read_from_input<R: Read>(
+    input: R,
+    eof: Option<&str>,
+) -> Result<String, String> {
+    let source = Reader::new(eof).read_from("stdin", input)?;
+    Ok(source.map(|source| source.text).unwrap_or_default())
 }
 
-/// Answer user's `--locator` options,
+/// Answer user's `--locator`, `--lang`, `--lexer` and `--no-pragmas` options,
 /// capture words that match the options from given long text.
 pub struct Captor {
-    patterns: Vec<Regex>,
+    locators: Vec<String>,
+    // Regex of the identifier itself, inserted between locator pairs.
+    word_pattern: String,
+    patterns: Vec<Pattern>,
+    // Patterns of language presets, only be used on sources
+    // whose language can be detected from their paths.
+    presets: HashMap<Language, Vec<Pattern>>,
+    // Masks comments and string literals before matching.
+    lexer: Option<Lexer>,
+    // Order captures by their first occurrences in sources,
+    // instead of by the order of patterns.
+    keep_order: bool,
+    // Honour inline `naming:ignore-*` directives in sources.
+    pragmas: bool,
+}
+
+/// A regex generated from a locator, with the tags of its capture groups,
+/// the n-th tag is the placeholder name of the (n+1)-th group.
+struct Pattern {
+    regex: Regex,
+    tags: Vec<Option<String>>,
+}
+
+/// One occurrence of a captured word,
+/// with the index of the pattern that matched it.
+struct Match {
+    word: String,
+    tag: Option<String>,
+    pattern_index: usize,
+    position: Position,
+}
+
+lazy_static! {
+    // "{}" or "{name}", but not quantifiers like "{2}" and "{1,3}".
+    static ref PLACEHOLDER: Regex =
+        Regex::new(r"\{([A-Za-z_][A-Za-z0-9_]*)?\}").unwrap();
 }
 
 impl Captor {
@@ -64,64 +492,608 @@ impl Captor {
         // Set default locator as word edge '\b'.
         let locators =
             locators.unwrap_or_else(|| vec![r"(?<=\b){}(?=\b)".to_string()]);
-        let mut patterns = Vec::new();
-        for locator in locators {
-            patterns.push(Captor::build_pattern_from(locator)?);
+        Ok(Captor {
+            patterns: Captor::build_patterns_from(&locators, DEFAULT_WORD)?,
+            locators,
+            word_pattern: DEFAULT_WORD.to_string(),
+            presets: HashMap::new(),
+            lexer: None,
+            keep_order: false,
+            pragmas: true,
+        })
+    }
+
+    /// Use the built-in locator preset of given language on all sources.
+    pub fn from_language(language: Language) -> Captor {
+        Captor {
+            locators: language.locators(),
+            word_pattern: DEFAULT_WORD.to_string(),
+            patterns: Captor::build_preset_of(language, DEFAULT_WORD),
+            presets: HashMap::new(),
+            lexer: None,
+            keep_order: false,
+            pragmas: true,
         }
-        Ok(Captor { patterns })
     }
 
-    fn build_pattern_from(locator: String) -> Result<Regex, String> {
-        let pair = locator.split_once("{}");
-        if pair.is_none() {
+    /// Capture the identifier tokens that given lexer recognises,
+    /// instead of matching locators on raw text.
+    pub fn from_lexer(lexer: Lexer) -> Captor {
+        Captor::new(None).unwrap().lexer(lexer.identifiers_only())
+    }
+
+    /// Let locators skip the comments and string literals
+    /// (unless the lexer is told to include them) in sources.
+    pub fn lexer(mut self, lexer: Lexer) -> Captor {
+        self.lexer = Some(lexer);
+        self
+    }
+
+    /// Let sources whose language can be detected from file extension
+    /// be captured with the language's preset instead,
+    /// other sources (like stdin) are still captured with current locators.
+    pub fn detect_language(mut self) -> Captor {
+        self.presets = Language::ALL
+            .iter()
+            .map(|language| {
+                let preset =
+                    Captor::build_preset_of(*language, &self.word_pattern);
+                (*language, preset)
+            })
+            .collect();
+        self
+    }
+
+    /// Answer user's `--keep-order` flag, let the order of captures
+    /// be same as the order of their first occurrences in sources.
+    pub fn keep_order(mut self, keep: bool) -> Captor {
+        self.keep_order = keep;
+        self
+    }
+
+    /// Answer user's `--no-pragmas` flag, whether inline directives
+    /// like `naming:ignore-next-line` are honoured (see `Pragmas`),
+    /// they are honoured by default.
+    pub fn pragmas(mut self, honour: bool) -> Captor {
+        self.pragmas = honour;
+        self
+    }
+
+    /// Answer user's `--word-pattern` option, set the regex that matches
+    /// the identifier itself between locator pairs, or one of
+    /// the built-in word patterns' names (see `WORD_PATTERNS`).
+    /// If the pattern is invalid or contains capture groups, return an Err.
+    pub fn word_pattern(mut self, pattern: &str) -> Result<Captor, String> {
+        let word = match WORD_PATTERNS.iter().find(|(name, _)| *name == pattern)
+        {
+            Some((_, word)) => word.to_string(),
+            None => Captor::check_word_pattern(pattern)?,
+        };
+        self.patterns = Captor::build_patterns_from(&self.locators, &word)?;
+        for (language, preset) in self.presets.iter_mut() {
+            *preset = Captor::build_preset_of(*language, &word);
+        }
+        self.word_pattern = word;
+        Ok(self)
+    }
+
+    fn check_word_pattern(pattern: &str) -> Result<String, String> {
+        let regex = Regex::new(pattern).map_err(|msg| {
+            format!("naming: word pattern `{}`: {}", pattern, msg)
+        })?;
+        // the whole match of the word pattern is the capture group 1
+        // in generated patterns, other groups would shift it.
+        if regex.captures_len() > 1 {
+            return Err(format!(
+                "naming: word pattern `{}`: must not contain capture groups, \
+                use non-capturing groups \"(?:...)\" instead.",
+                pattern
+            ));
+        }
+        Ok(format!("(?:{})", pattern))
+    }
+
+    fn build_preset_of(language: Language, word: &str) -> Vec<Pattern> {
+        Captor::build_patterns_from(&language.locators(), word)
+            .expect("built-in locator presets should be valid")
+    }
+
+    fn build_patterns_from(
+        locators: &[String],
+        word: &str,
+    ) -> Result<Vec<Pattern>, String> {
+        locators
+            .iter()
+            .map(|locator| Captor::build_pattern_from(locator, word))
+            .collect()
+    }
+
+    /// Replace each placeholder ("{}" or "{name}") in locator
+    /// with a capture group of the word pattern.
+    fn build_pattern_from(
+        locator: &str,
+        word: &str,
+    ) -> Result<Pattern, String> {
+        let mut pattern = String::new();
+        let mut tags = Vec::new();
+        let mut last_end = 0;
+        for placeholder in PLACEHOLDER.captures_iter(locator) {
+            let placeholder = placeholder.unwrap();
+            let whole = placeholder.get(0).unwrap();
+            pattern.push_str(&locator[last_end..whole.start()]);
+            pattern.push_str(&format!("({})", word));
+            tags.push(placeholder.get(1).map(|name| name.as_str().to_string()));
+            last_end = whole.end();
+        }
+        if tags.is_empty() {
             return Err(format!(
                 "naming: locator `{}`: can't split locator pair from this.",
                 locator
             ));
         }
-        let pair = pair.unwrap();
+        pattern.push_str(&locator[last_end..]);
 
-        // Currently, we use `([a-zA-Z0-9_-]+)` to match words, it's ok for now,
-        // because it could match words with any naming format.
-        Ok(Regex::new(&format!(r"{}([a-zA-Z0-9_-]+){}", pair.0, pair.1))
-            .unwrap())
+        let regex = Regex::new(&pattern)
+            .map_err(|msg| format!("naming: locator `{}`: {}", locator, msg))?;
+        // other groups would be mistaken for placeholders.
+        if regex.captures_len() != tags.len() + 1 {
+            return Err(format!(
+                "naming: locator `{}`: must not contain capture groups \
+                except placeholders, use non-capturing groups \"(?:...)\" instead.",
+                locator
+            ));
+        }
+        Ok(Pattern { regex, tags })
     }
 
     /// Extract words from given long text string,
     /// with regular expression and given locating prefix & suffix.
     pub fn capture_words(&self, text: Vec<String>) -> Vec<String> {
-        // apply matching on each file's content
-        let mut matches = self.get_matches_from(text);
-
-        // dedup while keep the order, what an elegant solution:
-        // https://users.rust-lang.org/t/deduplicate-vector-in-place-while-preserving-order/56568/6
-        let mut set = HashSet::new();
-        matches.retain(|word| set.insert(word.clone()));
-        matches
-    }
-
-    fn get_matches_from(&self, text: Vec<String>) -> Vec<String> {
-        text.iter()
-            .map(|t| {
-                // for each file's content, apply all patterns on it.
-                self.patterns
-                    .iter()
-                    .map(move |pattern| {
-                        pattern.captures_iter(t).into_iter().map(|cap| {
-                            cap.unwrap().get(1).unwrap().as_str().to_string()
-                        })
-                    })
-                    .flatten()
-                // now get one file's matches
+        self.capture_words_from(
+            text.into_iter().map(Source::from).collect::<Vec<Source>>(),
+        )
+    }
+
+    /// Same as `capture_words`, but sources with a detected language
+    /// are captured with the language's preset (see `detect_language`).
+    pub fn capture_words_from(&self, sources: Vec<Source>) -> Vec<String> {
+        self.capture(sources).into_iter().map(|capture| capture.word).collect()
+    }
+
+    /// Same as `capture_words_from`, but keep the positions
+    /// of each word's occurrences in sources, and the placeholder names
+    /// that captured it.
+    pub fn capture(&self, sources: Vec<Source>) -> Vec<Capture> {
+        let mut capturing = self.capturing();
+        for source in sources {
+            capturing.add(source);
+        }
+        capturing.finish()
+    }
+
+    /// Same as `capture`, but sources are passed one by one,
+    /// even piece by piece, see `Capturing`.
+    pub fn capturing(&self) -> Capturing<'_> {
+        Capturing {
+            captor: self,
+            pending: None,
+            occurrences: HashMap::new(),
+            count: 0,
+            ignored_words: HashSet::new(),
+            captures: Vec::new(),
+            indexes: HashMap::new(),
+        }
+    }
+
+    /// Matches in given source, with its pragmas, and the byte ranges
+    /// of comments and string literals if there is a lexer.
+    /// `in_block` means the source starts inside an ignored block.
+    fn matches_in(
+        &self,
+        source: &Source,
+        in_block: bool,
+    ) -> (Vec<Match>, Pragmas, Vec<Range<usize>>) {
+        let literals = self.lexer.as_ref().map(|lexer| lexer.literals(source));
+        let pragmas = if self.pragmas {
+            // with a lexer, only directives in comments are pragmas.
+            let comments = literals.as_ref().map(|(comments, _)| &comments[..]);
+            Pragmas::parse(&source.text, comments, in_block)
+        } else {
+            Pragmas::default()
+        };
+        let text = self.text_of(source, &pragmas.ignored);
+        let line_starts = line_starts_of(&source.text);
+        let mut matches = Vec::new();
+        // apply all patterns on the source.
+        for (pattern_index, pattern) in
+            self.patterns_for(source).iter().enumerate()
+        {
+            for cap in pattern.regex.captures_iter(&text) {
+                let cap = cap.unwrap();
+                // one match may capture several words with placeholders,
+                // optional placeholders may capture nothing.
+                for (i, tag) in pattern.tags.iter().enumerate() {
+                    if let Some(word) = cap.get(i + 1) {
+                        if pragmas.words.contains(word.as_str()) {
+                            continue;
+                        }
+                        matches.push(Match {
+                            word: word.as_str().to_string(),
+                            tag: tag.clone(),
+                            pattern_index,
+                            position: Position::new(
+                                source,
+                                &line_starts,
+                                word.start(),
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        let literals = literals
+            .map(|(comments, strings)| {
+                comments.into_iter().chain(strings).collect()
             })
-            .flatten()
-            .collect()
+            .unwrap_or_default();
+        (matches, pragmas, literals)
+    }
+
+    fn text_of<'a>(
+        &self,
+        source: &'a Source,
+        pragma_ignored: &[Range<usize>],
+    ) -> Cow<'a, str> {
+        let text = match &self.lexer {
+            None => Cow::Borrowed(source.text.as_str()),
+            Some(lexer) => Cow::Owned(lexer.mask(source)),
+        };
+        if source.ignored.is_empty() && pragma_ignored.is_empty() {
+            return text;
+        }
+        // blank out ignored ranges but keep line breaks,
+        // so that offsets of the rest text don't change.
+        let mut bytes = text.into_owned().into_bytes();
+        for range in source.ignored.iter().chain(pragma_ignored) {
+            bytes[range.clone()]
+                .iter_mut()
+                .filter(|byte| **byte != b'\n')
+                .for_each(|byte| *byte = b' ');
+        }
+        Cow::Owned(
+            String::from_utf8(bytes).expect("ranges are char boundaries"),
+        )
+    }
+
+    fn patterns_for(&self, source: &Source) -> &[Pattern] {
+        source
+            .language()
+            .and_then(|language| self.presets.get(&language))
+            .unwrap_or(&self.patterns)
+    }
+}
+
+/// Captures of sources that are passed one by one, and even piece by piece,
+/// so that a large input can be captured while it's read, without keeping
+/// its text in memory as a whole (see `Reader::capture_stdin`).
+///
+/// The text of a source is captured once enough of it is passed,
+/// locators are matched on it along with the text around it, so that
+/// they can look behind and ahead as far as `CONTEXT_LENGTH`.
+/// The text kept for the next time starts at a line that isn't inside
+/// a comment, a string literal or a pragma, so that the rest of the source
+/// is lexed and parsed same as it's in a whole. Otherwise, a line longer
+/// than `CONTEXT_LENGTH`, or a comment, a string literal or a pragma
+/// longer than `PIECE_LENGTH` (like an unclosed one) is split in the middle,
+/// so that the kept text is always bounded, and the text after the split
+/// is lexed on its own.
+pub struct Capturing<'a> {
+    captor: &'a Captor,
+    // the kept text of current source.
+    pending: Option<Pending>,
+    // occurrences of each word in current source, the count of them,
+    // and the words ignored by its pragmas.
+    occurrences: HashMap<String, Occurrences>,
+    count: usize,
+    ignored_words: HashSet<String>,
+    // captures of ended sources, and the index of each word in them.
+    captures: Vec<Capture>,
+    indexes: HashMap<String, usize>,
+}
+
+/// Occurrences of a word in the source being captured by `Capturing`,
+/// with the order keys of the first one and the first one of each tag.
+struct Occurrences {
+    first: (usize, usize),
+    tags: Vec<((usize, usize), String)>,
+    positions: Vec<Position>,
+}
+
+/// The kept text of the source being captured by `Capturing`.
+struct Pending {
+    source: Source,
+    // byte offset and line count of the text before it in the source,
+    // and the character count before it on its first line.
+    offset: usize,
+    lines: usize,
+    column: usize,
+    // the text before this byte offset has been captured.
+    captured: usize,
+    // whether it starts inside a `naming:ignore-start` block.
+    in_block: bool,
+}
+
+impl Pending {
+    fn new(source: Source) -> Pending {
+        Pending {
+            source,
+            offset: 0,
+            lines: 0,
+            column: 0,
+            captured: 0,
+            in_block: false,
+        }
+    }
+}
+
+impl Capturing<'_> {
+    /// Append the next piece of current source,
+    /// pass a piece after `end_source` to start a new source.
+    pub fn feed(&mut self, piece: Source) {
+        let pending = match &mut self.pending {
+            Some(pending) => {
+                pending.source.append(piece);
+                pending
+            }
+            None => self.pending.insert(Pending::new(piece)),
+        };
+        if pending.source.text.len() - pending.captured
+            >= PIECE_LENGTH + CONTEXT_LENGTH
+        {
+            self.settle(false);
+        }
+    }
+
+    /// Capture a whole source at once.
+    fn add(&mut self, source: Source) {
+        self.pending = Some(Pending::new(source));
+        self.end_source();
+    }
+
+    /// Capture the rest of current source.
+    pub fn end_source(&mut self) {
+        self.settle(true);
+        // words of `naming:ignore-word` are ignored in the whole source.
+        let words = std::mem::take(&mut self.ignored_words);
+        let mut occurrences: Vec<(String, Occurrences)> = self
+            .occurrences
+            .drain()
+            .filter(|(word, _)| !words.contains(word))
+            .collect();
+        // dedup while keep the order of first occurrences,
+        // and collect positions of the rest occurrences.
+        occurrences.sort_by_key(|(_, occurrences)| occurrences.first);
+        let captures = &mut self.captures;
+        for (word, mut occurrences) in occurrences {
+            let index =
+                *self.indexes.entry(word.clone()).or_insert_with(|| {
+                    captures.push(Capture::from(word));
+                    captures.len() - 1
+                });
+            let capture = &mut captures[index];
+            occurrences.tags.sort_by_key(|(key, _)| *key);
+            for (_, tag) in occurrences.tags {
+                if !capture.tags.contains(&tag) {
+                    capture.tags.push(tag);
+                }
+            }
+            // different patterns may capture the same occurrence.
+            occurrences.positions.sort_by_key(|position| position.offset);
+            occurrences.positions.dedup_by_key(|position| position.offset);
+            capture.positions.extend(occurrences.positions);
+        }
+        self.count = 0;
+        self.pending = None;
     }
+
+    /// Drop current source along with its captures,
+    /// like when it fails in the middle of reading.
+    pub fn discard_source(&mut self) {
+        self.pending = None;
+        self.occurrences.clear();
+        self.count = 0;
+        self.ignored_words.clear();
+    }
+
+    /// Captures of all ended sources, see `Captor::capture`.
+    pub fn finish(self) -> Vec<Capture> {
+        self.captures
+    }
+
+    /// Capture the kept text of current source, except a tail for locators
+    /// to look ahead if it isn't the last time, then drop the captured text
+    /// except the context for locators to look behind.
+    fn settle(&mut self, last: bool) {
+        let pending = match &mut self.pending {
+            Some(pending) => pending,
+            None => return,
+        };
+        let text = &pending.source.text;
+        let end = if last {
+            text.len()
+        } else {
+            let end = text.len().saturating_sub(CONTEXT_LENGTH);
+            let end = floor_char_boundary(text, end);
+            // a line longer than the context is split in the middle.
+            let line_start = line_start_at(text, end);
+            if end - line_start > CONTEXT_LENGTH {
+                end
+            } else {
+                line_start
+            }
+        };
+        if end <= pending.captured && !last {
+            return;
+        }
+
+        let (matches, pragmas, literals) =
+            self.captor.matches_in(&pending.source, pending.in_block);
+        for Match { word, tag, pattern_index, mut position } in matches {
+            if position.offset < pending.captured || position.offset >= end {
+                continue;
+            }
+            if position.line == 1 {
+                position.column += pending.column;
+            }
+            position.line += pending.lines;
+            position.offset += pending.offset;
+            // with `keep_order`, merge the matches of all patterns
+            // by their offsets, as if they were matched in a single
+            // left-to-right scan, otherwise as if the whole source were
+            // matched with the patterns in turn.
+            let key = if self.captor.keep_order {
+                (position.offset, self.count)
+            } else {
+                (pattern_index, self.count)
+            };
+            self.count += 1;
+            let occurrences =
+                self.occurrences.entry(word).or_insert_with(|| Occurrences {
+                    first: key,
+                    tags: Vec::new(),
+                    positions: Vec::new(),
+                });
+            occurrences.first = occurrences.first.min(key);
+            if let Some(tag) = tag {
+                match occurrences.tags.iter_mut().find(|(_, t)| *t == tag) {
+                    Some((first, _)) => *first = (*first).min(key),
+                    None => occurrences.tags.push((key, tag)),
+                }
+            }
+            occurrences.positions.push(position);
+        }
+        let Pragmas { ignored, blocks, words } = pragmas;
+        self.ignored_words.extend(words);
+        if last {
+            return;
+        }
+
+        // pragma blocks are entered again with `in_block`,
+        // other ranges can't be split.
+        let unsplittable: Vec<Range<usize>> = literals
+            .into_iter()
+            .chain(ignored.into_iter().filter(|range| !blocks.contains(range)))
+            .collect();
+        let restart = restart_of(text, end, unsplittable);
+        let inside =
+            |range: &Range<usize>| range.start < restart && restart < range.end;
+        pending.in_block = blocks.iter().any(inside);
+        let line_start = line_start_at(text, restart);
+        let column = text[line_start..restart].chars().count();
+        pending.column = match line_start {
+            0 => pending.column + column,
+            _ => column,
+        };
+        pending.lines += text[..restart].matches('\n').count();
+        pending.offset += restart;
+        pending.captured = end - restart;
+        pending.source.text.drain(..restart);
+        pending.source.ignored = pending
+            .source
+            .ignored
+            .iter()
+            .filter(|range| range.end > restart)
+            .map(|range| {
+                range.start.max(restart) - restart..range.end - restart
+            })
+            .collect();
+    }
+}
+
+/// Where the kept text restarts for the text after `end`, a line start
+/// that isn't inside any of `unsplittable` ranges, as near as possible
+/// to `end - CONTEXT_LENGTH` but not before it, or the nearest one before it
+/// within `PIECE_LENGTH`. Otherwise a char boundary outside the ranges,
+/// or inside them at last, so that the kept text is always bounded.
+fn restart_of(
+    text: &str,
+    end: usize,
+    mut unsplittable: Vec<Range<usize>>,
+) -> usize {
+    unsplittable.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::new();
+    for range in unsplittable {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => {
+                last.end = last.end.max(range.end);
+            }
+            _ => merged.push(range),
+        }
+    }
+    let bytes = text.as_bytes();
+    let is_free = |start: &usize| {
+        let i = merged.partition_point(|range| range.start < *start);
+        text.is_char_boundary(*start) && (i == 0 || merged[i - 1].end <= *start)
+    };
+    let is_line_start = |start: &usize| {
+        (*start == 0 || bytes[start - 1] == b'\n') && is_free(start)
+    };
+    let from = floor_char_boundary(text, end.saturating_sub(CONTEXT_LENGTH));
+    (from..=end)
+        .find(is_line_start)
+        .or_else(|| {
+            (from.saturating_sub(PIECE_LENGTH)..from).rev().find(is_line_start)
+        })
+        .or_else(|| (from..=end).find(is_free))
+        .unwrap_or(from)
+}
+
+/// Built-in word patterns that can be passed to `--word-pattern` by name.
+pub const WORD_PATTERNS: [(&str, &str); 5] = [
+    // UAX #31 identifier characters and hyphens, it could match
+    // words with any naming format, but not pure numbers like "1_000".
+    ("default", DEFAULT_WORD),
+    // Identifiers of most programming languages, without hyphens.
+    ("ident", r"(?:\p{XID_Start}|_)\p{XID_Continue}*"),
+    // Dot-separated keys of configurations, like "kebab.case" in
+    // "kebab.case-key = 1", the dots are kept in captured words.
+    (
+        "dotted",
+        r"(?![\p{Nd}.-]+(?![\p{XID_Continue}.-]))[\p{XID_Continue}-]+(?:\.[\p{XID_Continue}-]+)*",
+    ),
+    // Identifiers with sigils and predicate suffixes in Ruby, Perl and PHP,
+    // like "$scope", "@ivar" and "empty?", the sigils are kept in captured words.
+    // Note that the default locator's "\b" doesn't stand beside sigils.
+    ("sigil", r"[$@]{0,2}\p{XID_Continue}+[?!]?"),
+    // Phrases of words separated by single spaces, like labels "Page size"
+    // and headings "Page Size", usually used with locators like "label='{}'".
+    ("phrase", r"\p{XID_Continue}+(?: \p{XID_Continue}+)*"),
+];
+
+const DEFAULT_WORD: &str =
+    r"[\p{XID_Continue}-]*[\p{XID_Continue}--[\p{Nd}_]][\p{XID_Continue}-]*";
+
+/// Byte offset of the first character of the line where `offset` is in.
+fn line_start_at(text: &str, offset: usize) -> usize {
+    text[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// Byte offsets of the first character of each line.
+fn line_starts_of(text: &str) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+    starts
 }
 
 #[cfg(test)]
 mod stdin_reader_tests {
-    use crate::extraction::read_from_input;
+    use std::io::{self, Read};
+
+    use crate::extraction::{
+        read_from_input, Reader, Source, BINARY_SNIFFING_LENGTH, CHUNK_LENGTH,
+        CONTEXT_LENGTH,
+    };
+    use crate::marker::Markers;
 
     #[test]
     fn read_from_input_without_logical_eof() {
@@ -137,6 +1109,115 @@ mod stdin_reader_tests {
         assert_eq!(actual.unwrap(), "before-a ");
     }
 
+    #[test]
+    fn stop_reading_at_logical_eof() {
+        struct BrokenInput;
+        impl Read for BrokenInput {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("read after eof"))
+            }
+        }
+
+        let head = "before-a end after-a".to_string() + &" ".repeat(10000);
+        let input = io::BufReader::new(head.as_bytes().chain(BrokenInput));
+        let actual = read_from_input(input, Some("end"));
+        assert_eq!(actual.unwrap(), "before-a ");
+    }
+
+    #[test]
+    fn stop_reading_at_regex_logical_eof() {
+        struct BrokenInput;
+        impl Read for BrokenInput {
+            fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("read after eof"))
+            }
+        }
+
+        // a regex occurrence is found once the context after it is read,
+        // the head for sniffing binary is read before it anyway.
+        let head = "before-a end after-a".to_string()
+            + &" ".repeat(BINARY_SNIFFING_LENGTH as usize + CONTEXT_LENGTH);
+        let input = head.as_bytes().chain(BrokenInput);
+        let markers = Markers::new(true).eof(r"\bend\b").unwrap();
+        let source =
+            Reader::new(None).markers(markers).read_from("stdin", input);
+        assert_eq!(source.unwrap().unwrap().text, "before-a ");
+    }
+
+    #[test]
+    fn apply_markers_on_large_input_piece_by_piece() {
+        let lines = "x\n".repeat(CONTEXT_LENGTH * 2);
+        let input = format!("a <{}> b <c", lines);
+        let markers = Markers::new(false).region("<{}>").unwrap();
+        let mut pieces = Vec::new();
+        let is_text = Reader::new(None).markers(markers).read_pieces_from(
+            "stdin",
+            input.as_bytes(),
+            &mut |piece| pieces.push(piece),
+        );
+        assert!(is_text.unwrap());
+        assert!(pieces.len() > 1);
+
+        let mut source = Source::from(String::new());
+        pieces.into_iter().for_each(|piece| source.append(piece));
+        assert_eq!(source.text, input);
+        let n = lines.len();
+        assert_eq!(source.ignored, vec![0..3, 3 + n..3 + n + 5]);
+    }
+
+    #[test]
+    fn retry_interrupted_reading() {
+        // interrupted once, then reads "tail" and ends.
+        struct InterruptedInput(usize);
+        impl Read for InterruptedInput {
+            fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+                self.0 += 1;
+                match self.0 {
+                    1 => Err(io::Error::from(io::ErrorKind::Interrupted)),
+                    2 => b"tail".as_ref().read(buffer),
+                    _ => Ok(0),
+                }
+            }
+        }
+
+        let head = " ".repeat(10000);
+        let input = head.as_bytes().chain(InterruptedInput(0));
+        let actual = read_from_input(io::BufReader::new(input), None);
+        assert_eq!(actual.unwrap(), head + "tail");
+    }
+
+    #[test]
+    fn apply_markers_when_reading() {
+        let input = "a\nSTART b <c> d <e>\nEND f".as_bytes();
+        let markers = Markers::new(false)
+            .sof("START")
+            .unwrap()
+            .eof("END")
+            .unwrap()
+            .region("<{}>")
+            .unwrap();
+        let source =
+            Reader::new(None).markers(markers).read_from("stdin", input);
+        let source = source.unwrap().unwrap();
+        assert_eq!(source.text, "a\nSTART b <c> d <e>\n");
+        assert_eq!(source.ignored, vec![0..7, 7..11, 12..17, 18..20]);
+    }
+
+    #[test]
+    fn regex_markers_straddling_chunk_boundaries() {
+        // the head is 8 KiB, and the following chunks are shorter.
+        for boundary in [8192, 8192 + CHUNK_LENGTH] {
+            let head = "x ".repeat((boundary - 4) / 2);
+            let input = head.clone() + "STOPPED pageSize\nSTOP";
+            let markers = Markers::new(true).eof(r"\bSTOP\b").unwrap();
+            let source = Reader::new(None)
+                .markers(markers)
+                .read_from("stdin", input.as_bytes());
+            let source = source.unwrap().unwrap();
+            assert_eq!(source.text, head + "STOPPED pageSize\n");
+        }
+    }
+
     #[test]
     fn take_whole_input_when_logical_eof_not_exists() {
         let input = b"before-a end after-a";
@@ -148,12 +1229,15 @@ mod stdin_reader_tests {
 #[cfg(test)]
 mod captor_tests {
     use crate::to_string_vec;
+    use crate::{Language, Lexer, Syntax};
 
-    use super::Captor;
+    use super::{
+        Captor, Capture, Position, Source, CONTEXT_LENGTH, PIECE_LENGTH,
+    };
 
     #[test]
     fn return_empty_vec_when_no_match() {
-        let text = to_string_vec(vec!["不能被匹配"]);
+        let text = to_string_vec(vec!["，。！？"]);
         let actual = Captor::new(None).unwrap().capture_words(text);
         assert_eq!(actual, Vec::<String>::new())
     }
@@ -166,10 +1250,126 @@ mod captor_tests {
             Captor::new(Some(to_string_vec(vec![r"\s{}\s*=", r"\s{}\s*;"])))
                 .unwrap()
                 .capture_words(text);
-        let expect: Vec<String> = to_string_vec(vec!["i", "s", "1"]);
+        let expect: Vec<String> = to_string_vec(vec!["i", "s"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn multiple_placeholders_capture_several_words() {
+        let text = vec![Source::from(
+            "let pageSize = maxSize; let count = pageSize;".to_string(),
+        )];
+        let actual =
+            Captor::new(Some(to_string_vec(vec![r"let\s+{}\s*=\s*{}\s*;"])))
+                .unwrap()
+                .capture(text);
+        let words: Vec<&str> =
+            actual.iter().map(|capture| capture.word.as_str()).collect();
+        assert_eq!(words, vec!["pageSize", "maxSize", "count"]);
+        assert!(actual.iter().all(|capture| capture.tags.is_empty()));
+        assert_eq!(actual[0].positions.len(), 2);
+    }
+
+    #[test]
+    fn named_placeholders_tag_captures() {
+        let text = vec![Source::from(
+            "struct A { size: usize, max_size: Size, usize: u8 }".to_string(),
+        )];
+        let actual = Captor::new(Some(to_string_vec(vec![
+            r"(?<=[{,])\s*{name}:\s*{type}\b",
+        ])))
+        .unwrap()
+        .keep_order(true)
+        .capture(text);
+        let tagged: Vec<(&str, Vec<&str>)> = actual
+            .iter()
+            .map(|capture| {
+                let tags = capture.tags.iter().map(|tag| tag.as_str());
+                (capture.word.as_str(), tags.collect())
+            })
+            .collect();
+        assert_eq!(
+            tagged,
+            vec![
+                ("size", vec!["name"]),
+                ("usize", vec!["type", "name"]),
+                ("max_size", vec!["name"]),
+                ("Size", vec!["type"]),
+                ("u8", vec!["type"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn capture_group_in_locator_is_an_error() {
+        let locators = to_string_vec(vec![r"(let|var)\s+{}"]);
+        assert!(Captor::new(Some(locators)).is_err());
+        // quantifiers aren't placeholders.
+        let locators = to_string_vec(vec![r"\s{1,2}{}\s{2}="]);
+        assert!(Captor::new(Some(locators)).is_ok());
+    }
+
+    #[test]
+    fn built_in_word_patterns_work() {
+        let text = to_string_vec(vec![
+            "$scope = @ivar if empty? and kebab.case-key = 1.5 or x-1",
+        ]);
+        let capture = |locator: &str, word_pattern: &str| {
+            Captor::new(Some(to_string_vec(vec![locator])))
+                .unwrap()
+                .word_pattern(word_pattern)
+                .unwrap()
+                .capture_words(text.clone())
+        };
+
+        let actual = capture(r"(?<![\w$@]){}(?![\w?!])", "sigil");
+        let expect = to_string_vec(vec![
+            "$scope", "@ivar", "if", "empty?", "and", "kebab", "case", "key",
+            "1", "5", "or", "x",
+        ]);
+        assert_eq!(actual, expect);
+
+        let actual = capture(r"(?<=\b){}(?=\b)", "dotted");
+        let expect = to_string_vec(vec![
+            "scope",
+            "ivar",
+            "if",
+            "empty",
+            "and",
+            "kebab.case-key",
+            "or",
+            "x-1",
+        ]);
+        assert_eq!(actual, expect);
+
+        let actual = capture(r"(?<=\b){}(?=\b)", "ident");
+        let expect = to_string_vec(vec![
+            "scope", "ivar", "if", "empty", "and", "kebab", "case", "key",
+            "or", "x",
+        ]);
         assert_eq!(actual, expect);
     }
 
+    #[test]
+    fn custom_word_pattern_works() {
+        let text = to_string_vec(vec!["let a_1 = b; let C = d;"]);
+        let actual = Captor::new(Some(to_string_vec(vec![r"let\s+{}\s*="])))
+            .unwrap()
+            .word_pattern("[a-z]+|[a-z]_[0-9]")
+            .unwrap()
+            .capture_words(text);
+        assert_eq!(actual, to_string_vec(vec!["a_1"]));
+    }
+
+    #[test]
+    fn word_pattern_with_capture_group_is_an_error() {
+        let captor = || Captor::new(None).unwrap();
+        assert!(captor().word_pattern("([a-z]+)").is_err());
+        assert!(captor().word_pattern("(?P<word>[a-z]+)").is_err());
+        assert!(captor().word_pattern("[a-z").is_err());
+        assert!(captor().word_pattern("(?:[a-z]+)").is_ok());
+    }
+
     #[test]
     fn custom_captor_works() {
         let text = to_string_vec(vec!["@now#can$be&matched"]);
@@ -184,11 +1384,26 @@ mod captor_tests {
         assert_eq!(actual, expect);
     }
 
+    #[test]
+    fn keep_order_of_first_occurrences() {
+        let text = to_string_vec(vec!["@now#can$be&matched", "@first#"]);
+        let locators: Vec<String> =
+            to_string_vec(vec![r"#{}\$", "@{}#", r"\${}&", r"&{}\z"]);
+
+        let actual = Captor::new(Some(locators))
+            .unwrap()
+            .keep_order(true)
+            .capture_words(text);
+        let expect: Vec<String> =
+            to_string_vec(vec!["now", "can", "be", "matched", "first"]);
+        assert_eq!(actual, expect);
+    }
+
     #[test]
     fn duplicating_matches_are_removed() {
         let text = to_string_vec(vec![
-            "let a = 1; let b = 2; let c = 3;",
-            "let a = 1; let b = 2; let c = 3;",
+            "let a = x; let b = y; let c = z;",
+            "let a = x; let b = y; let c = z;",
         ]);
         let actual =
             Captor::new(Some(to_string_vec(vec![r"\s{}\s*=", r"\s{}\s*;"])))
@@ -196,7 +1411,15 @@ mod captor_tests {
                 .capture_words(text);
         // notice that the result order is based on option order.
         let expect: Vec<String> =
-            to_string_vec(vec!["a", "b", "c", "1", "2", "3"]);
+            to_string_vec(vec!["a", "b", "c", "x", "y", "z"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn can_capture_unicode_identifiers() {
+        let text = to_string_vec(vec!["größe = ÉtatCourant + 变量_名;"]);
+        let actual = Captor::new(None).unwrap().capture_words(text);
+        let expect = to_string_vec(vec!["größe", "ÉtatCourant", "变量_名"]);
         assert_eq!(actual, expect);
     }
 
@@ -215,4 +1438,245 @@ mod captor_tests {
         ]);
         assert_eq!(actual, expect);
     }
+
+    #[test]
+    fn all_language_presets_are_valid() {
+        for language in Language::ALL.iter() {
+            Captor::from_language(*language);
+        }
+    }
+
+    #[test]
+    fn language_preset_works() {
+        let text = to_string_vec(vec![
+            "fn page_size(total: usize, mut per_page: usize) -> usize {\n\
+                let mut count = total / per_page;\n\
+                std::cmp::max(count, 1)\n\
+            }",
+        ]);
+        let actual = Captor::from_language(Language::Rust).capture_words(text);
+        let expect =
+            to_string_vec(vec!["page_size", "count", "total", "per_page"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn detect_language_from_source_path() {
+        let text = "def load(path: str):\n    content = path";
+        let sources = vec![
+            Source {
+                path: Some("a.py".to_string()),
+                ..Source::from(text.to_string())
+            },
+            Source {
+                path: Some("a.txt".to_string()),
+                ..Source::from(text.to_string())
+            },
+        ];
+        let actual = Captor::new(None)
+            .unwrap()
+            .detect_language()
+            .capture_words_from(sources);
+        // the .txt file is captured with default locator.
+        let expect =
+            to_string_vec(vec!["load", "content", "path", "def", "str"]);
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn lexer_captor_skips_comments_and_strings() {
+        let text = to_string_vec(vec![
+            "int count = 1; // count of items\n\
+            char* name = \"not an identifier\";",
+        ]);
+        let lexer = Lexer::new(Some(Syntax::CLike));
+        let actual = Captor::from_lexer(lexer).capture_words(text.clone());
+        assert_eq!(actual, to_string_vec(vec!["count", "name"]));
+
+        let actual = Captor::new(Some(to_string_vec(vec![r"\s{}\s*="])))
+            .unwrap()
+            .lexer(lexer.include_strings(true))
+            .capture_words(text);
+        assert_eq!(actual, to_string_vec(vec!["count", "name"]));
+    }
+
+    #[test]
+    fn skip_ignored_ranges_of_sources() {
+        let sources = vec![Source {
+            ignored: vec![0..2, 4..8],
+            ..Source::from("a\nb c\nd e".to_string())
+        }];
+        let actual = Captor::new(None).unwrap().capture(sources);
+        let words: Vec<&str> =
+            actual.iter().map(|capture| capture.word.as_str()).collect();
+        assert_eq!(words, vec!["b", "e"]);
+        assert_eq!(actual[1].positions[0].line, 3);
+    }
+
+    #[test]
+    fn honour_inline_pragmas() {
+        let text = "// naming:ignore-word fooBar\nfooBar pageSize\n\
+            // naming:ignore-next-line\nmaxSize\nminSize";
+        let captor = Captor::new(None).unwrap();
+        assert_eq!(
+            captor.capture_words(vec![text.to_string()]),
+            to_string_vec(vec!["pageSize", "minSize"])
+        );
+        assert!(captor
+            .pragmas(false)
+            .capture_words(vec![text.to_string()])
+            .contains(&"maxSize".to_string()));
+
+        // with a lexer, directives in string literals are not pragmas.
+        let text = "s = \"naming:ignore-next-line\";\nmaxSize = 1;";
+        assert!(Captor::new(None)
+            .unwrap()
+            .lexer(Lexer::new(Some(Syntax::CLike)))
+            .capture_words(vec![text.to_string()])
+            .contains(&"maxSize".to_string()));
+    }
+
+    /// Capture the text both as a whole and piece by piece,
+    /// the kept text never grows beyond the bound.
+    fn capture_piece_by_piece(captor: &Captor, text: &str) -> Vec<Capture> {
+        let expect = captor.capture(vec![Source::from(text.to_string())]);
+
+        let mut capturing = captor.capturing();
+        let mut start = 0;
+        while start < text.len() {
+            let mut end = (start + 100).min(text.len());
+            while !text.is_char_boundary(end) {
+                end += 1;
+            }
+            capturing.feed(Source::from(text[start..end].to_string()));
+            let kept = capturing.pending.as_ref().unwrap().source.text.len();
+            assert!(kept <= (PIECE_LENGTH + CONTEXT_LENGTH) * 2 + 100);
+            start = end;
+        }
+        capturing.end_source();
+        let actual = capturing.finish();
+        assert_eq!(actual, expect);
+        actual
+    }
+
+    fn words_of(captures: &[Capture]) -> Vec<&str> {
+        captures.iter().map(|capture| capture.word.as_str()).collect()
+    }
+
+    #[test]
+    fn capture_large_source_piece_by_piece() {
+        let mut text = String::new();
+        let mut lines = 0;
+        let mut lines_until = |text: &mut String, length: usize| {
+            while text.len() < length {
+                *text += &format!(
+                    "let value_{} = \"not_{}\"; // note_{}\n",
+                    lines, lines, lines
+                );
+                lines += 1;
+            }
+        };
+        // the kept text restarts around `PIECE_LENGTH - CONTEXT_LENGTH`
+        // and twice of it, which are inside the block and the comment.
+        lines_until(&mut text, PIECE_LENGTH / 2);
+        text += "// naming:ignore-start\n";
+        text += &"let ignored_value = 1;\n".repeat(PIECE_LENGTH / 32);
+        text += "// naming:ignore-end\n";
+        lines_until(&mut text, PIECE_LENGTH * 3 / 2);
+        text += "/* it's a comment";
+        text += &" long_comment\n".repeat(PIECE_LENGTH / 16);
+        text += "*/\n";
+        lines_until(&mut text, PIECE_LENGTH * 4);
+        text += "// naming:ignore-word value_7\n";
+        let last_word = format!("value_{}", lines - 1);
+
+        for keep_order in [false, true] {
+            let captor = Captor::new(None)
+                .unwrap()
+                .lexer(Lexer::new(Some(Syntax::CLike)))
+                .keep_order(keep_order);
+            let actual = capture_piece_by_piece(&captor, &text);
+
+            let words = words_of(&actual);
+            assert!(words.contains(&last_word.as_str()));
+            for word in
+                ["value_7", "not_1", "note_1", "long_comment", "ignored_value"]
+            {
+                assert!(!words.contains(&word), "{}", word);
+            }
+        }
+    }
+
+    #[test]
+    fn capture_single_line_piece_by_piece() {
+        let text: String = (0..PIECE_LENGTH)
+            .map(|i| format!("变量_{} ", i))
+            .collect::<String>();
+        assert!(!text.contains('\n') && text.len() > PIECE_LENGTH * 4);
+
+        let actual = capture_piece_by_piece(&Captor::new(None).unwrap(), &text);
+        assert_eq!(actual.len(), PIECE_LENGTH);
+        let last = &actual[PIECE_LENGTH - 1].positions[0];
+        assert_eq!(last.line, 1);
+        let before = &text[..text.rfind("变量_").unwrap()];
+        assert_eq!(last.column, before.chars().count() + 1);
+    }
+
+    #[test]
+    fn capture_unclosed_literal_piece_by_piece() {
+        let text = "let a = x;\nlet s = \"".to_string()
+            + &"b c\n".repeat(PIECE_LENGTH * 2);
+        let captor =
+            Captor::new(None).unwrap().lexer(Lexer::new(Some(Syntax::CLike)));
+        let expect = captor.capture(vec![Source::from(text.clone())]);
+        assert_eq!(words_of(&expect), vec!["let", "a", "x", "s"]);
+
+        // the literal is split to bound the kept text,
+        // and the words after the split are captured.
+        let mut capturing = captor.capturing();
+        for line in text.split_inclusive('\n') {
+            capturing.feed(Source::from(line.to_string()));
+            let kept = capturing.pending.as_ref().unwrap().source.text.len();
+            assert!(kept <= (PIECE_LENGTH + CONTEXT_LENGTH) * 2);
+        }
+        capturing.end_source();
+        let actual = capturing.finish();
+        assert_eq!(words_of(&actual[..4]), vec!["let", "a", "x", "s"]);
+    }
+
+    #[test]
+    fn invalid_regex_in_locator_is_an_error() {
+        assert!(Captor::new(Some(to_string_vec(vec!["({}"]))).is_err());
+    }
+
+    #[test]
+    fn capture_positions_of_all_occurrences() {
+        let sources = vec![
+            Source {
+                path: Some("a".to_string()),
+                ..Source::from("x = y\n变量 y".to_string())
+            },
+            Source::from("y".to_string()),
+        ];
+        let actual = Captor::new(None).unwrap().capture(sources);
+
+        let position = |path: Option<&str>, line, column, offset| Position {
+            path: path.map(|path| path.to_string()),
+            line,
+            column,
+            offset,
+        };
+        assert_eq!(actual[0].word, "x");
+        assert_eq!(actual[0].positions, vec![position(Some("a"), 1, 1, 0)]);
+        assert_eq!(actual[1].word, "y");
+        assert_eq!(
+            actual[1].positions,
+            vec![
+                position(Some("a"), 1, 5, 4),
+                // column is counted in characters.
+                position(Some("a"), 2, 4, 13),
+                position(None, 1, 1, 0),
+            ]
+        );
+    }
 }
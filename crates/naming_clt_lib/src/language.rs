@@ -0,0 +1,476 @@
+use std::path::Path;
+
+/// Answer user's `--lang` option,
+/// languages that have built-in locator presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Language {
+    Rust,
+    Java,
+    Python,
+    Go,
+    JavaScript,
+    C,
+}
+
+impl Language {
+    pub const ALL: [Language; 6] = [
+        Language::Rust,
+        Language::Java,
+        Language::Python,
+        Language::Go,
+        Language::JavaScript,
+        Language::C,
+    ];
+
+    /// Find the language with the name that user passes to `--lang` option.
+    pub fn from_name(name: &str) -> Result<Language, String> {
+        match name.to_lowercase().as_str() {
+            "rust" | "rs" => Ok(Language::Rust),
+            "java" => Ok(Language::Java),
+            "python" | "py" => Ok(Language::Python),
+            "go" => Ok(Language::Go),
+            "js" | "javascript" => Ok(Language::JavaScript),
+            "c" => Ok(Language::C),
+            _ => Err(format!("naming: language `{}`: no preset for it.", name)),
+        }
+    }
+
+    /// Guess the language of a file by its extension,
+    /// return None when the extension isn't a known one.
+    pub fn from_path(path: &str) -> Option<Language> {
+        let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "rs" => Some(Language::Rust),
+            "java" => Some(Language::Java),
+            "py" | "pyi" => Some(Language::Python),
+            "go" => Some(Language::Go),
+            "js" | "mjs" | "cjs" | "jsx" | "ts" | "tsx" => {
+                Some(Language::JavaScript)
+            }
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" => Some(Language::C),
+            _ => None,
+        }
+    }
+
+    /// Locator pairs that capture the identifiers declared in this language
+    /// (declarations, assignments, parameters and fields),
+    /// in the same "prefix{}suffix" form as `--locator` option's values.
+    pub fn locators(&self) -> Vec<String> {
+        let preset: &[&str] = match self {
+            Language::Rust => &RUST,
+            Language::Java => &JAVA,
+            Language::Python => &PYTHON,
+            Language::Go => &GO,
+            Language::JavaScript => &JAVASCRIPT,
+            Language::C => &C,
+        };
+        preset.iter().map(|locator| locator.to_string()).collect()
+    }
+
+    /// Reserved words, primitive and common built-in type names,
+    /// and other names that come with this language rather than
+    /// user's project, like "self" and "String".
+    pub fn keywords(&self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &RUST_KEYWORDS,
+            Language::Java => &JAVA_KEYWORDS,
+            Language::Python => &PYTHON_KEYWORDS,
+            Language::Go => &GO_KEYWORDS,
+            Language::JavaScript => &JAVASCRIPT_KEYWORDS,
+            Language::C => &C_KEYWORDS,
+        }
+    }
+
+    /// Keywords that lead statements which only refer to existing names,
+    /// like "return" and "import" in Java, rather than declare new ones.
+    pub fn referring_keywords(&self) -> &'static [&'static str] {
+        match self {
+            Language::Rust => &["return"],
+            Language::Java => &["package", "import", "return", "throw"],
+            Language::Python => &["return", "raise", "del", "global"],
+            Language::Go => &["package", "return", "goto"],
+            Language::JavaScript => &["return", "throw"],
+            Language::C => &["return", "goto"],
+        }
+    }
+
+    /// Whether the word is one of `keywords` in the file of given path,
+    /// C++'s own keywords (like "class") count in C++ files.
+    pub fn is_keyword(&self, word: &str, path: Option<&str>) -> bool {
+        self.keywords().contains(&word)
+            || (*self == Language::C
+                && path.is_some_and(is_cpp_file)
+                && CPP_KEYWORDS.contains(&word))
+    }
+}
+
+// Presets are written with fancy-regex syntax, note that
+// a lookbehind must have a constant length,
+// and there must be no capture group in prefix or suffix.
+
+const RUST: [&str; 4] = [
+    // declarations
+    r"\b(?:fn|struct|enum|trait|type|union|mod|const|static)\s+(?:mut\s+)?{}\b",
+    // assignments
+    r"\blet\s+(?:mut\s+)?{}\b",
+    // parameters and fields
+    r"[(,{]\s*(?:pub(?:\([\w:\s]*\))?\s+)?(?:mut\s+)?{}\s*:(?!:)",
+    // closure parameters
+    r"\|\s*(?:mut\s+)?{}\s*[|,:]",
+];
+
+const JAVA: [&str; 4] = [
+    // declarations
+    r"\b(?:class|interface|enum|record)\s+{}\b",
+    // methods
+    r"[\w>\]]\s+(?!(?:if|for|while|switch|catch|synchronized|new|return)\b){}\s*\([^()]*\)\s*(?:throws\s+[\w.,\s]+)?\{",
+    // variables, fields and parameters
+    r"(?<=[\w>\]])(?<!return)(?<!package)(?<!import)(?<!throw)\s+{}\s*(?:=(?!=)|[;,):])",
+    // the rest variables in a multiple declaration
+    r",\s*{}\s*=(?!=)",
+];
+
+const PYTHON: [&str; 5] = [
+    // declarations
+    r"\b(?:def|class)\s+{}\b",
+    // assignments, with optional type hint
+    r"(?m)^[ \t]*{}\s*(?::[^=\n]*)?(?:=(?!=)|[-+*/|&]=)",
+    // fields
+    r"\bself\.{}\s*(?::[^=\n]*)?=(?!=)",
+    // loop variables
+    r"\bfor\s+{}\s+in\b",
+    // parameters
+    r"[(,]\s*\*{0,2}{}\s*(?::|=(?!=))",
+];
+
+const GO: [&str; 5] = [
+    // declarations
+    r"\b(?:package|func|type|var|const)\s+{}\b",
+    // methods
+    r"\bfunc\s*\([^)]*\)\s*{}\b",
+    // short variable declarations
+    r"(?:\b|,\s*){}\s*(?:,\s*[\w-]+\s*)*:=",
+    // parameters
+    r"[(,]\s*{}\s+(?:\.\.\.)?[*\[\]\w.]+\s*[,)]",
+    // fields
+    r"(?m)^[ \t]+{}\s+[*\[\]\w.]+\s*(?:`[^`\n]*`)?[ \t]*$",
+];
+
+const JAVASCRIPT: [&str; 6] = [
+    // declarations
+    r"\b(?:function\*?|class)\s+{}\b",
+    // assignments
+    r"\b(?:var|let|const)\s+{}\b",
+    // fields
+    r"\bthis\.{}\s*=(?!=)",
+    // object keys and methods
+    r"(?m)^[ \t]*(?:async\s+|static\s+|get\s+|set\s+)*{}\s*(?::|\([^()]*\)\s*\{)",
+    // parameters
+    r"\bfunction\b[^(]*\(\s*{}\s*[,)=]",
+    r"(?:\(\s*|\b){}\s*\)?\s*=>",
+];
+
+const C: [&str; 5] = [
+    // declarations
+    r"\b(?:struct|union|enum)\s+{}\b",
+    r"#\s*define\s+{}\b",
+    r"\}\s*{}\s*;",
+    // variables, functions and parameters
+    r"(?<=[\w\]*])(?<!return)(?<!sizeof)\s+\**(?!(?:if|for|while|switch|return|sizeof)\b){}\s*(?:=(?!=)|[;,)\[(])",
+    // the rest variables in a multiple declaration
+    r",\s*\**{}\s*(?:=(?!=)|[;\[])",
+];
+
+const RUST_KEYWORDS: [&str; 75] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn",
+    "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let",
+    "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+    "Self", "static", "struct", "super", "trait", "true", "type", "union",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do",
+    "final", "macro", "override", "priv", "try", "typeof", "unsized",
+    "virtual", "yield", "bool", "char", "str", "i8", "i16", "i32", "i64",
+    "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64",
+    "String", "Vec", "Option", "Some", "None", "Box",
+];
+
+const JAVA_KEYWORDS: [&str; 65] = [
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "var",
+    "record",
+    "yield",
+    "sealed",
+    "permits",
+    "true",
+    "false",
+    "null",
+    "String",
+    "Object",
+    "Integer",
+    "Long",
+    "Boolean",
+    "Double",
+    "Override",
+];
+
+const PYTHON_KEYWORDS: [&str; 50] = [
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break",
+    "class", "continue", "def", "del", "elif", "else", "except", "finally",
+    "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal",
+    "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+    "match", "case", "self", "cls", "int", "float", "str", "bool", "bytes",
+    "list", "dict", "set", "tuple", "object", "print",
+];
+
+const GO_KEYWORDS: [&str; 56] = [
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+    "true",
+    "false",
+    "nil",
+    "iota",
+    "bool",
+    "byte",
+    "rune",
+    "string",
+    "error",
+    "int",
+    "int8",
+    "int16",
+    "int32",
+    "int64",
+    "uint",
+    "uint8",
+    "uint16",
+    "uint32",
+    "uint64",
+    "uintptr",
+    "float32",
+    "float64",
+    "complex64",
+    "complex128",
+    "any",
+    "append",
+    "cap",
+    "len",
+    "make",
+    "new",
+    "panic",
+];
+
+const JAVASCRIPT_KEYWORDS: [&str; 62] = [
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "new",
+    "return",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+    "let",
+    "static",
+    "enum",
+    "await",
+    "async",
+    "of",
+    "get",
+    "set",
+    "true",
+    "false",
+    "null",
+    "undefined",
+    "NaN",
+    "Infinity",
+    "arguments",
+    "interface",
+    "type",
+    "implements",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "number",
+    "string",
+    "boolean",
+    "any",
+    "unknown",
+    "never",
+    "object",
+];
+
+const C_KEYWORDS: [&str; 39] = [
+    "auto", "break", "case", "char", "const", "continue", "default", "do",
+    "double", "else", "enum", "extern", "float", "for", "goto", "if", "inline",
+    "int", "long", "register", "restrict", "return", "short", "signed",
+    "sizeof", "static", "struct", "switch", "typedef", "union", "unsigned",
+    "void", "volatile", "while", "bool", "true", "false", "NULL", "size_t",
+];
+
+/// C++ files share the preset of C, these words are only
+/// keywords in them, see `Language::is_keyword`.
+pub(crate) const CPP_KEYWORDS: [&str; 12] = [
+    "class",
+    "namespace",
+    "template",
+    "typename",
+    "this",
+    "public",
+    "private",
+    "protected",
+    "virtual",
+    "new",
+    "delete",
+    "nullptr",
+];
+
+fn is_cpp_file(path: &str) -> bool {
+    let extension = Path::new(path).extension().and_then(|e| e.to_str());
+    extension.is_some_and(|extension| {
+        ["cc", "cpp", "cxx", "hpp"].contains(&extension.to_lowercase().as_str())
+    })
+}
+
+#[cfg(test)]
+mod language_tests {
+    use super::Language;
+
+    #[test]
+    fn find_language_by_name() {
+        assert_eq!(Language::from_name("rust"), Ok(Language::Rust));
+        assert_eq!(Language::from_name("JS"), Ok(Language::JavaScript));
+        assert!(Language::from_name("cobol").is_err());
+    }
+
+    #[test]
+    fn detect_language_from_file_extension() {
+        assert_eq!(Language::from_path("src/main.rs"), Some(Language::Rust));
+        assert_eq!(Language::from_path("a/B.java"), Some(Language::Java));
+        assert_eq!(Language::from_path("lib.PY"), Some(Language::Python));
+        assert_eq!(Language::from_path("main.go"), Some(Language::Go));
+        assert_eq!(Language::from_path("app.ts"), Some(Language::JavaScript));
+        assert_eq!(Language::from_path("x.h"), Some(Language::C));
+        assert_eq!(Language::from_path("notes.txt"), None);
+        assert_eq!(Language::from_path("Makefile"), None);
+    }
+
+    #[test]
+    fn keywords_of_languages() {
+        assert!(Language::Rust.keywords().contains(&"self"));
+        assert!(Language::Java.keywords().contains(&"String"));
+        assert!(!Language::Python.keywords().contains(&"page_size"));
+    }
+
+    #[test]
+    fn referring_keywords_are_keywords() {
+        for language in Language::ALL.iter() {
+            for keyword in language.referring_keywords() {
+                assert!(language.is_keyword(keyword, None), "{}", keyword);
+            }
+        }
+    }
+
+    #[test]
+    fn cpp_keywords_only_in_cpp_files() {
+        assert!(Language::C.is_keyword("int", None));
+        assert!(!Language::C.is_keyword("class", None));
+        assert!(!Language::C.is_keyword("new", Some("list.c")));
+        assert!(Language::C.is_keyword("new", Some("list.cpp")));
+        assert!(Language::C.is_keyword("nullptr", Some("list.HPP")));
+    }
+}
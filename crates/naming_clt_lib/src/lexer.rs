@@ -0,0 +1,599 @@
+use std::ops::Range;
+use std::path::Path;
+
+use unicode_xid::UnicodeXID;
+
+use crate::extraction::Source;
+use crate::language::Language;
+
+/// Language families that share the same comment and string literal syntax,
+/// answer user's `--lexer` option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Syntax {
+    /// `//` and `/* */` comments, `"..."`, `'c'` and `` `...` `` literals.
+    CLike,
+    /// `//` and `/* */` comments, `'...'`, `"..."` and `` `...` `` literals.
+    JsLike,
+    /// `#` comments, `'...'`, `"..."` and triple-quoted literals.
+    PythonLike,
+    /// `#` comments, `'...'` and `"..."` literals.
+    ShellLike,
+}
+
+impl Syntax {
+    /// Find the syntax with the name that user passes to `--lexer` option.
+    pub fn from_name(name: &str) -> Result<Syntax, String> {
+        match name.to_lowercase().as_str() {
+            "c" => Ok(Syntax::CLike),
+            "js" => Ok(Syntax::JsLike),
+            "python" => Ok(Syntax::PythonLike),
+            "shell" => Ok(Syntax::ShellLike),
+            _ => Err(format!("naming: lexer `{}`: no such syntax.", name)),
+        }
+    }
+
+    /// Guess the syntax of a file by its extension,
+    /// return None when the extension isn't a known one.
+    pub fn from_path(path: &str) -> Option<Syntax> {
+        let extension = Path::new(path).extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "sh" | "bash" | "zsh" | "ksh" | "fish" => Some(Syntax::ShellLike),
+            _ => Language::from_path(path).map(Syntax::of),
+        }
+    }
+
+    /// The family that given language belongs to.
+    pub fn of(language: Language) -> Syntax {
+        match language {
+            Language::Python => Syntax::PythonLike,
+            Language::JavaScript => Syntax::JsLike,
+            _ => Syntax::CLike,
+        }
+    }
+
+    /// The language whose keywords are skipped when a source's own
+    /// language is unknown, None for shell-like syntax.
+    fn default_language(self) -> Option<Language> {
+        match self {
+            Syntax::CLike => Some(Language::C),
+            Syntax::JsLike => Some(Language::JavaScript),
+            Syntax::PythonLike => Some(Language::Python),
+            Syntax::ShellLike => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Identifier,
+    Number,
+    String,
+    Comment,
+    Punctuation,
+}
+
+/// A token in text, `start` and `end` are byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Answer user's `--lexer` option, blank out the comments and string literals
+/// in sources before `Captor` matches locators on them,
+/// so that words inside them won't be captured.
+///
+/// Locators are usually written for declarations, so the names in
+/// a statement that only refers to existing names (see
+/// `Language::referring_keywords`) are blanked out too, like
+/// "package data" in Java's "package data;" and "return a - b;".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lexer {
+    // None means detecting the syntax from each source's file extension.
+    syntax: Option<Syntax>,
+    // None means detecting the language from each source's file extension.
+    language: Option<Language>,
+    include_comments: bool,
+    include_strings: bool,
+    identifiers_only: bool,
+}
+
+impl Lexer {
+    pub fn new(syntax: Option<Syntax>) -> Lexer {
+        Lexer {
+            syntax,
+            language: None,
+            include_comments: false,
+            include_strings: false,
+            identifiers_only: false,
+        }
+    }
+
+    /// Take the keywords of given language rather than
+    /// the one detected from each source's file extension,
+    /// answer user's `--lang` option.
+    pub fn language(mut self, language: Language) -> Lexer {
+        self.language = Some(language);
+        self
+    }
+
+    /// Keep the words inside comments.
+    pub fn include_comments(mut self, include: bool) -> Lexer {
+        self.include_comments = include;
+        self
+    }
+
+    /// Keep the words inside string literals.
+    pub fn include_strings(mut self, include: bool) -> Lexer {
+        self.include_strings = include;
+        self
+    }
+
+    /// Also blank out numbers, punctuations and language keywords,
+    /// so that only user's identifier tokens remain in masked text.
+    pub(crate) fn identifiers_only(mut self) -> Lexer {
+        self.identifiers_only = true;
+        self
+    }
+
+    /// Replace the characters of unwanted tokens with spaces,
+    /// line breaks and the byte offsets of remaining tokens are kept.
+    pub fn mask(&self, source: &Source) -> String {
+        let syntax = self.syntax_of(source);
+        let language = self
+            .language
+            .or_else(|| source.language())
+            .or_else(|| syntax.default_language());
+        let path = source.path.as_deref();
+        let is_keyword = |word: &str| match language {
+            Some(language) if self.identifiers_only => {
+                language.is_keyword(word, path)
+            }
+            _ => false,
+        };
+        // all identifier tokens are kept without locators.
+        let is_referring = |word: &str| match language {
+            Some(language) if !self.identifiers_only => {
+                language.referring_keywords().contains(&word)
+            }
+            _ => false,
+        };
+
+        // every masked token is a run of whole characters,
+        // so replacing all its bytes with spaces keeps the text valid UTF-8.
+        let mut bytes = source.text.clone().into_bytes();
+        // the line end of the referring statement that is being blanked out,
+        // the statement also ends at a ";" or a block's "{".
+        let mut referring_until = None;
+        for token in tokenize(&source.text, syntax) {
+            let word = &source.text[token.start..token.end];
+            if let Some(line_end) = referring_until {
+                if token.start >= line_end || word == ";" || word == "{" {
+                    referring_until = None;
+                }
+            }
+            let is_identifier = token.kind == TokenKind::Identifier;
+            if is_identifier && is_referring(word) {
+                let rest = &source.text[token.end..];
+                referring_until =
+                    Some(token.end + rest.find('\n').unwrap_or(rest.len()));
+            }
+            // the keyword itself is blanked out along with the names.
+            let is_referred = is_identifier && referring_until.is_some();
+            let is_keyword = is_identifier && is_keyword(word);
+            if is_keyword || is_referred || self.should_mask(token.kind) {
+                bytes[token.start..token.end]
+                    .iter_mut()
+                    .filter(|byte| **byte != b'\n')
+                    .for_each(|byte| *byte = b' ');
+            }
+        }
+        String::from_utf8(bytes).unwrap()
+    }
+
+    /// Byte ranges of the comments and the string literals in given source.
+    pub(crate) fn literals(
+        &self,
+        source: &Source,
+    ) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+        let mut comments = Vec::new();
+        let mut strings = Vec::new();
+        for token in tokenize(&source.text, self.syntax_of(source)) {
+            match token.kind {
+                TokenKind::Comment => comments.push(token.start..token.end),
+                TokenKind::String => strings.push(token.start..token.end),
+                _ => {}
+            }
+        }
+        (comments, strings)
+    }
+
+    fn syntax_of(&self, source: &Source) -> Syntax {
+        self.syntax
+            .or_else(|| source.path.as_deref().and_then(Syntax::from_path))
+            .unwrap_or(Syntax::CLike)
+    }
+
+    fn should_mask(&self, kind: TokenKind) -> bool {
+        match kind {
+            TokenKind::Identifier => false,
+            TokenKind::Comment => !self.include_comments,
+            TokenKind::String => !self.include_strings,
+            TokenKind::Number | TokenKind::Punctuation => self.identifiers_only,
+        }
+    }
+}
+
+/// Split text into tokens, whitespaces are skipped.
+pub fn tokenize(text: &str, syntax: Syntax) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    while let Some(c) = text[start..].chars().next() {
+        if c.is_whitespace() {
+            start += c.len_utf8();
+            continue;
+        }
+        let (kind, end) = next_token(text, start, c, syntax);
+        tokens.push(Token { kind, start, end });
+        start = end;
+    }
+    tokens
+}
+
+fn next_token(
+    text: &str,
+    start: usize,
+    c: char,
+    syntax: Syntax,
+) -> (TokenKind, usize) {
+    let rest = &text[start..];
+    if let Some(end) = comment_end(text, start, syntax) {
+        return (TokenKind::Comment, end);
+    }
+    if let Some(end) = string_end(rest, syntax) {
+        return (TokenKind::String, start + end);
+    }
+    if is_identifier_start(c, syntax) {
+        let end = start + take_while(rest, |c| is_identifier_part(c, syntax));
+        // literal prefixes like python's f"..." or rust's r#"..."#
+        if let Some(len) =
+            prefixed_string_len(&text[start..end], &text[end..], syntax)
+        {
+            return (TokenKind::String, end + len);
+        }
+        return (TokenKind::Identifier, end);
+    }
+    if c.is_ascii_digit() {
+        let len =
+            take_while(rest, |c| c.is_alphanumeric() || c == '_' || c == '.');
+        return (TokenKind::Number, start + len);
+    }
+    (TokenKind::Punctuation, start + c.len_utf8())
+}
+
+fn comment_end(text: &str, start: usize, syntax: Syntax) -> Option<usize> {
+    let rest = &text[start..];
+    let to_line_end = || start + rest.find('\n').unwrap_or(rest.len());
+    match syntax {
+        Syntax::CLike | Syntax::JsLike if rest.starts_with("//") => {
+            Some(to_line_end())
+        }
+        Syntax::CLike | Syntax::JsLike if rest.starts_with("/*") => Some(
+            rest[2..].find("*/").map_or(text.len(), |end| start + 2 + end + 2),
+        ),
+        Syntax::PythonLike if rest.starts_with('#') => Some(to_line_end()),
+        // "$#" and "${#var}" aren't comments.
+        Syntax::ShellLike if rest.starts_with('#') => {
+            match text[..start].chars().last() {
+                None => Some(to_line_end()),
+                Some(c) if c.is_whitespace() || ";|&(".contains(c) => {
+                    Some(to_line_end())
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Length of the string literal at the start of text.
+fn string_end(rest: &str, syntax: Syntax) -> Option<usize> {
+    match (syntax, rest.chars().next()?) {
+        (Syntax::PythonLike, quote @ ('"' | '\'')) => {
+            let triple = quote.to_string().repeat(3);
+            if rest.starts_with(&triple) {
+                Some(rest[3..].find(&triple).map_or(rest.len(), |end| end + 6))
+            } else {
+                Some(quoted_len(rest, quote, true))
+            }
+        }
+        (Syntax::CLike, quote @ ('"' | '`')) => {
+            Some(quoted_len(rest, quote, true))
+        }
+        // tell char literals from rust's lifetimes like `'a`
+        (Syntax::CLike, '\'') => char_literal_len(rest),
+        (Syntax::JsLike, quote @ ('"' | '\'' | '`')) => {
+            Some(quoted_len(rest, quote, true))
+        }
+        (Syntax::ShellLike, '"') => Some(quoted_len(rest, '"', true)),
+        (Syntax::ShellLike, '\'') => Some(quoted_len(rest, '\'', false)),
+        _ => None,
+    }
+}
+
+/// Length of the string literal starts with given quote,
+/// the whole rest text if it isn't closed.
+fn quoted_len(rest: &str, quote: char, escapable: bool) -> usize {
+    let mut escaped = false;
+    for (i, c) in rest.char_indices().skip(1) {
+        if escaped {
+            escaped = false;
+        } else if escapable && c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return i + c.len_utf8();
+        }
+    }
+    rest.len()
+}
+
+fn char_literal_len(rest: &str) -> Option<usize> {
+    let mut chars = rest.char_indices().skip(1);
+    match chars.next()? {
+        (_, '\\') => {
+            let end = rest[2..].find('\'')?;
+            // an escape sequence is short, like '\n' or '\u{1F600}'
+            if end <= 10 {
+                Some(2 + end + 1)
+            } else {
+                None
+            }
+        }
+        (_, '\'') => None,
+        _ => match chars.next()? {
+            (i, '\'') => Some(i + 1),
+            _ => None,
+        },
+    }
+}
+
+// in any letter case, like "Rb" or "F"
+const PYTHON_STRING_PREFIXES: [&str; 8] =
+    ["r", "u", "b", "f", "br", "rb", "fr", "rf"];
+
+fn prefixed_string_len(
+    prefix: &str,
+    rest: &str,
+    syntax: Syntax,
+) -> Option<usize> {
+    match syntax {
+        Syntax::PythonLike
+            if PYTHON_STRING_PREFIXES
+                .contains(&prefix.to_lowercase().as_str()) =>
+        {
+            string_end(rest, syntax)
+        }
+        Syntax::CLike if ["b", "L", "u", "U", "u8"].contains(&prefix) => {
+            rest.starts_with('"').then(|| quoted_len(rest, '"', true))
+        }
+        Syntax::CLike if prefix == "r" || prefix == "br" => {
+            let hashes = take_while(rest, |c| c == '#');
+            if !rest[hashes..].starts_with('"') {
+                return None;
+            }
+            let closing = "\"".to_string() + &rest[..hashes];
+            Some(
+                rest[hashes + 1..]
+                    .find(&closing)
+                    .map_or(rest.len(), |end| hashes + 1 + end + closing.len()),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn is_identifier_start(c: char, syntax: Syntax) -> bool {
+    let has_dollar = syntax == Syntax::CLike || syntax == Syntax::JsLike;
+    c.is_xid_start() || c == '_' || (c == '$' && has_dollar)
+}
+
+fn is_identifier_part(c: char, syntax: Syntax) -> bool {
+    is_identifier_start(c, syntax) || c.is_xid_continue()
+}
+
+/// Byte length of the leading characters that satisfy the predicate.
+fn take_while(text: &str, predicate: impl Fn(char) -> bool) -> usize {
+    text.find(|c| !predicate(c)).unwrap_or(text.len())
+}
+
+#[cfg(test)]
+mod lexer_tests {
+    use crate::extraction::Source;
+    use crate::language::Language;
+
+    use super::{tokenize, Lexer, Syntax, TokenKind};
+
+    fn kinds_of(text: &str, syntax: Syntax) -> Vec<(TokenKind, &str)> {
+        tokenize(text, syntax)
+            .into_iter()
+            .map(|token| (token.kind, &text[token.start..token.end]))
+            .collect()
+    }
+
+    fn source_of(text: &str) -> Source {
+        Source::from(text.to_string())
+    }
+
+    #[test]
+    fn tokenize_c_like_text() {
+        use TokenKind::*;
+        let text = "let s = \"a \\\" b\"; // note\n/* block */ x'c'";
+        assert_eq!(
+            kinds_of(text, Syntax::CLike),
+            vec![
+                (Identifier, "let"),
+                (Identifier, "s"),
+                (Punctuation, "="),
+                (String, "\"a \\\" b\""),
+                (Punctuation, ";"),
+                (Comment, "// note"),
+                (Comment, "/* block */"),
+                (Identifier, "x"),
+                (String, "'c'"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_js_like_text() {
+        use TokenKind::*;
+        let text = "const fs = require('fs'); let s = 'hello world';";
+        assert_eq!(
+            kinds_of(text, Syntax::JsLike),
+            vec![
+                (Identifier, "const"),
+                (Identifier, "fs"),
+                (Punctuation, "="),
+                (Identifier, "require"),
+                (Punctuation, "("),
+                (String, "'fs'"),
+                (Punctuation, ")"),
+                (Punctuation, ";"),
+                (Identifier, "let"),
+                (Identifier, "s"),
+                (Punctuation, "="),
+                (String, "'hello world'"),
+                (Punctuation, ";"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tell_rust_lifetime_from_char_literal() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds_of("&'a str, '\\n', r#\"raw\"#", Syntax::CLike),
+            vec![
+                (Punctuation, "&"),
+                (Punctuation, "'"),
+                (Identifier, "a"),
+                (Identifier, "str"),
+                (Punctuation, ","),
+                (String, "'\\n'"),
+                (Punctuation, ","),
+                (String, "r#\"raw\"#"),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_python_like_text() {
+        use TokenKind::*;
+        let text = "x = f'{y}' # note\n\"\"\"doc\n\"\"\"";
+        assert_eq!(
+            kinds_of(text, Syntax::PythonLike),
+            vec![
+                (Identifier, "x"),
+                (Punctuation, "="),
+                (String, "f'{y}'"),
+                (Comment, "# note"),
+                (String, "\"\"\"doc\n\"\"\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_real_python_prefixes_start_strings() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds_of("Rb'a' uu'b'", Syntax::PythonLike),
+            vec![(String, "Rb'a'"), (Identifier, "uu"), (String, "'b'")]
+        );
+    }
+
+    #[test]
+    fn hash_in_shell_expansion_is_not_comment() {
+        use TokenKind::*;
+        assert_eq!(
+            kinds_of("echo $# 'it''s' # note", Syntax::ShellLike),
+            vec![
+                (Identifier, "echo"),
+                (Punctuation, "$"),
+                (Punctuation, "#"),
+                (String, "'it'"),
+                (String, "'s'"),
+                (Comment, "# note"),
+            ]
+        );
+    }
+
+    #[test]
+    fn mask_keeps_byte_offsets_and_line_breaks() {
+        let text = "a = \"变量\" // b\nc";
+        let actual = Lexer::new(Some(Syntax::CLike)).mask(&source_of(text));
+        assert_eq!(actual.len(), text.len());
+        assert_eq!(actual, format!("a ={}\nc", " ".repeat(14)));
+    }
+
+    #[test]
+    fn mask_with_included_comments_and_strings() {
+        let text = "a = \"b\" // c";
+        let lexer = Lexer::new(Some(Syntax::CLike))
+            .include_comments(true)
+            .include_strings(true);
+        assert_eq!(lexer.mask(&source_of(text)), text);
+        assert_eq!(
+            lexer.identifiers_only().mask(&source_of(text)),
+            "a   \"b\" // c"
+        );
+    }
+
+    #[test]
+    fn identifiers_only_mask_skips_keywords() {
+        let text = "int count; String name;";
+        let lexer = Lexer::new(Some(Syntax::CLike)).identifiers_only();
+        assert_eq!(lexer.mask(&source_of(text)), "    count  String name ");
+        assert_eq!(
+            lexer.language(Language::Java).mask(&source_of(text)),
+            "    count         name "
+        );
+    }
+
+    #[test]
+    fn mask_names_in_referring_statements() {
+        let text = "package data;\nint count = a - b; return a - b;\nc;";
+        let lexer = Lexer::new(Some(Syntax::CLike)).language(Language::Java);
+        assert_eq!(
+            lexer.mask(&source_of(text)),
+            "            ;\nint count = a - b;          -  ;\nc;"
+        );
+        // a block ends the statement.
+        let text = "return new Runnable() { int count; }";
+        assert_eq!(
+            lexer.mask(&source_of(text)),
+            "                   () { int count; }"
+        );
+        // all identifiers are kept without locators.
+        let text = "return a;";
+        assert_eq!(
+            lexer.identifiers_only().mask(&source_of(text)),
+            "       a "
+        );
+    }
+
+    #[test]
+    fn detect_syntax_from_source_path() {
+        let source = Source {
+            path: Some("run.sh".to_string()),
+            ..Source::from("a # b".to_string())
+        };
+        assert_eq!(Lexer::new(None).mask(&source), "a    ");
+
+        let source = Source {
+            path: Some("app.js".to_string()),
+            ..Source::from("a('b c')".to_string())
+        };
+        assert_eq!(Lexer::new(None).mask(&source), "a(     )");
+    }
+}
@@ -3,56 +3,111 @@ extern crate lazy_static;
 
 use std::collections::HashMap;
 
+pub use case::{DigitPolicy, Format, HUNGARIAN_PREFIXES};
 pub use conversion::*;
 pub use extraction::*;
-use naming_lib::NamingCase;
+pub use language::*;
+pub use lexer::{Lexer, Syntax};
+pub use marker::Markers;
+pub use stopword::Stopwords;
+pub use traversal::Walker;
 
+mod case;
 mod conversion;
 mod extraction;
+mod language;
+mod lexer;
+mod marker;
+mod pragma;
+mod stopword;
+mod traversal;
 
 /// Intermediate type for converting
-/// NamingCase type to String in different output format.
+/// identifiers to String in different output format,
+/// with the options of splitting and converting words.
+#[derive(Clone, Copy)]
 struct Formatter {
-    pub inner: fn(&NamingCase) -> String,
+    pub inner: fn(&str, &case::CaseOptions) -> String,
 }
 
-impl Clone for Formatter {
-    fn clone(&self) -> Self {
-        Formatter { inner: self.inner }
-    }
-}
-
-impl Copy for Formatter {}
-
 lazy_static! {
-    // default option of `--filter` and `--output`, i.e. allow all formats.
+    // default option of `--output`, the most common formats.
     static ref DEFAULT_OPTIONS: Vec<String> = to_string_vec(vec!["S", "s", "k", "c", "p"]);
 
-    // used for converting NamingCase to String type in iteration.
+    // default option of `--filter`, i.e. allow all formats.
+    static ref DEFAULT_FILTER: Vec<String> = to_string_vec(
+        vec!["S", "s", "k", "c", "p", "T", "K", "d", "r", "f", "F", "A", "t", "e"]
+    );
+
+    // used for converting identifiers to String type in iteration.
     static ref DIRECT_MAPPERS: HashMap<&'static str, Formatter> = {
         let mut map: HashMap<&'static str, Formatter> = HashMap::new();
-        map.insert("S", Formatter{inner:|case| case.to_screaming_snake().unwrap()});
-        map.insert("s", Formatter{inner:|case| case.to_snake().unwrap()});
-        map.insert("k", Formatter{inner:|case| case.to_kebab().unwrap()});
-        map.insert("c", Formatter{inner:|case| case.to_camel().unwrap()});
-        map.insert("p", Formatter{inner:|case| case.to_pascal().unwrap()});
+        map.insert("S", Formatter{inner:|word, options| case::to_screaming_snake(word, options)});
+        map.insert("s", Formatter{inner:|word, options| case::to_snake(word, options)});
+        map.insert("k", Formatter{inner:|word, options| case::to_kebab(word, options)});
+        map.insert("c", Formatter{inner:|word, options| case::to_camel(word, options)});
+        map.insert("p", Formatter{inner:|word, options| case::to_pascal(word, options)});
+        map.insert("T", Formatter{inner:|word, options| case::to_train(word, options)});
+        map.insert("K", Formatter{inner:|word, options| case::to_cobol(word, options)});
+        map.insert("d", Formatter{inner:|word, options| case::to_dot(word, options)});
+        map.insert("r", Formatter{inner:|word, options| case::to_path(word, options)});
+        map.insert("f", Formatter{inner:|word, options| case::to_flat(word, options)});
+        map.insert("F", Formatter{inner:|word, options| case::to_upper_flat(word, options)});
+        map.insert("A", Formatter{inner:|word, options| case::to_ada(word, options)});
+        map.insert("t", Formatter{inner:|word, options| case::to_title_case(word, options)});
+        map.insert("e", Formatter{inner:|word, options| case::to_sentence_case(word, options)});
         map
     };
 
     // same as above.
     static ref JSON_MAPPERS:HashMap<&'static str, Formatter> = {
         fn compose(key:&str,value:String) -> String {
-              "\"".to_string() + key + "\":\"" + &value + "\""
+              "\"".to_string() + key + "\":\"" + &escape_json(&value) + "\""
         }
 
         let mut map: HashMap<&'static str, Formatter> = HashMap::new();
         map.insert("S", Formatter{
-            inner:|case| compose("screaming_snake",case.to_screaming_snake().unwrap())
+            inner:|word, options| compose("screaming_snake",case::to_screaming_snake(word, options))
+        });
+        map.insert("s", Formatter{
+            inner:|word, options| compose("snake",case::to_snake(word, options))
+        });
+        map.insert("k", Formatter{
+            inner:|word, options| compose("kebab",case::to_kebab(word, options))
+        });
+        map.insert("c", Formatter{
+            inner:|word, options| compose("camel",case::to_camel(word, options))
+        });
+        map.insert("p", Formatter{
+            inner:|word, options| compose("pascal",case::to_pascal(word, options))
+        });
+        map.insert("T", Formatter{
+            inner:|word, options| compose("train",case::to_train(word, options))
+        });
+        map.insert("K", Formatter{
+            inner:|word, options| compose("cobol",case::to_cobol(word, options))
+        });
+        map.insert("d", Formatter{
+            inner:|word, options| compose("dot",case::to_dot(word, options))
+        });
+        map.insert("r", Formatter{
+            inner:|word, options| compose("path",case::to_path(word, options))
+        });
+        map.insert("f", Formatter{
+            inner:|word, options| compose("flat",case::to_flat(word, options))
+        });
+        map.insert("F", Formatter{
+            inner:|word, options| compose("upper_flat",case::to_upper_flat(word, options))
+        });
+        map.insert("A", Formatter{
+            inner:|word, options| compose("ada",case::to_ada(word, options))
+        });
+        map.insert("t", Formatter{
+            inner:|word, options| compose("title",case::to_title_case(word, options))
+        });
+        map.insert("e", Formatter{
+            inner:|word, options| compose("sentence",case::to_sentence_case(word, options))
         });
-        map.insert("s", Formatter{inner:|case| compose("snake",case.to_snake().unwrap())});
-        map.insert("k", Formatter{inner:|case| compose("kebab",case.to_kebab().unwrap())});
-        map.insert("c", Formatter{inner:|case| compose("camel",case.to_camel().unwrap())});
-        map.insert("p", Formatter{inner:|case| compose("pascal",case.to_pascal().unwrap())});
         map
     };
 }
@@ -60,3 +115,22 @@ lazy_static! {
 pub fn to_string_vec(ori: Vec<&str>) -> Vec<String> {
     ori.iter().map(|str| str.to_string()).collect()
 }
+
+/// Escape the characters that can't directly appear in a json string.
+fn escape_json(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                escaped.push_str(&format!("\\u{:04x}", c as u32))
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,375 @@
+use std::ops::Range;
+
+use fancy_regex::Regex;
+
+/// A logical marker in input, a literal string or a regex.
+enum Marker {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl Marker {
+    /// Byte range of the first occurrence at or after `from`.
+    fn find_in(&self, text: &str, from: usize) -> Option<Range<usize>> {
+        match self {
+            Marker::Literal(marker) => text[from..]
+                .find(marker.as_str())
+                .map(|start| from + start..from + start + marker.len()),
+            Marker::Regex(regex) => regex
+                .find_from_pos(text, from)
+                .ok()
+                .flatten()
+                .map(|found| found.start()..found.end()),
+        }
+    }
+}
+
+/// Answer user's `--sof`, `--eof`, `--region` and `--regex-markers` options,
+/// find out which parts of each input file should be ignored.
+#[derive(Default)]
+pub struct Markers {
+    regex: bool,
+    sof: Option<Marker>,
+    eof: Option<Marker>,
+    regions: Vec<(Marker, Marker)>,
+}
+
+impl Markers {
+    /// Markers are literal strings, or regexes (with fancy-regex syntax)
+    /// if `regex` is true.
+    pub fn new(regex: bool) -> Markers {
+        Markers { regex, ..Markers::default() }
+    }
+
+    /// Ignore the text before the end of the first occurrence of given marker,
+    /// the whole text is ignored if it doesn't occur.
+    pub fn sof(mut self, marker: &str) -> Result<Markers, String> {
+        self.sof = Some(self.build_marker(marker)?);
+        Ok(self)
+    }
+
+    /// Ignore the text from the start of the first occurrence of given marker
+    /// (after the logical start), the rest of input is never read.
+    pub fn eof(mut self, marker: &str) -> Result<Markers, String> {
+        self.eof = Some(self.build_marker(marker)?);
+        Ok(self)
+    }
+
+    /// Only keep the text inside regions between a begin marker
+    /// and the next end marker, in "begin{}end" form.
+    /// A region without an end marker lasts to the end of the text.
+    pub fn region(mut self, pair: &str) -> Result<Markers, String> {
+        let (begin, end) = pair.split_once("{}").ok_or_else(|| {
+            format!(
+                "naming: region `{}`: can't split region pair from this.",
+                pair
+            )
+        })?;
+        if begin.is_empty() || end.is_empty() {
+            return Err(format!(
+                "naming: region `{}`: begin and end markers can't be empty.",
+                pair
+            ));
+        }
+        let region = (self.build_marker(begin)?, self.build_marker(end)?);
+        self.regions.push(region);
+        Ok(self)
+    }
+
+    fn build_marker(&self, marker: &str) -> Result<Marker, String> {
+        if !self.regex {
+            return Ok(Marker::Literal(marker.to_string()));
+        }
+        Regex::new(marker)
+            .map(Marker::Regex)
+            .map_err(|msg| format!("naming: marker `{}`: {}", marker, msg))
+    }
+
+    /// How many bytes after the start of an occurrence have to be read
+    /// before it can be found, None for regex markers, whose occurrences
+    /// may depend on the text behind them (like "\bSTOP\b" or lookaheads).
+    pub(crate) fn lookahead(&self) -> Option<usize> {
+        if self.regex {
+            return None;
+        }
+        let markers = self.sof.iter().chain(&self.eof).chain(
+            self.regions.iter().flat_map(|(begin, end)| vec![begin, end]),
+        );
+        Some(
+            markers
+                .map(|marker| match marker {
+                    Marker::Literal(literal) => literal.len(),
+                    Marker::Regex(_) => unreachable!("markers are literal"),
+                })
+                .max()
+                .unwrap_or(0),
+        )
+    }
+
+    /// Start applying markers on an input that is read piece by piece.
+    pub(crate) fn scan(&self) -> Scan<'_> {
+        Scan {
+            markers: self,
+            sof: if self.sof.is_none() { Some(0) } else { None },
+            sof_from: 0,
+            eof: None,
+            eof_from: 0,
+            regions: vec![RegionScan::default(); self.regions.len()],
+            kept: Vec::new(),
+        }
+    }
+}
+
+/// Where the search of a region pair is, in a `Scan`.
+#[derive(Clone, Default)]
+struct RegionScan {
+    // start of the inner range of the region that hasn't been closed yet.
+    open: Option<usize>,
+    // search the next begin (or end if it's open) marker from here.
+    from: usize,
+}
+
+/// The progress of applying `Markers` on an input that is read piece by piece.
+/// Occurrences that start before a settled offset are applied, the rest are
+/// searched again after more input is read.
+///
+/// All offsets are byte offsets in the whole input, and the text passed to
+/// `advance` should start at or before the offset that has been settled,
+/// so that markers can look behind, and be long enough after the offset
+/// to be settled, so that markers can look ahead.
+pub(crate) struct Scan<'a> {
+    markers: &'a Markers,
+    // end of the logical start, None if it hasn't occurred yet.
+    sof: Option<usize>,
+    sof_from: usize,
+    eof: Option<usize>,
+    eof_from: usize,
+    regions: Vec<RegionScan>,
+    // inner ranges of closed regions that haven't been passed by `ignored`.
+    kept: Vec<Range<usize>>,
+}
+
+impl Scan<'_> {
+    /// Apply the occurrences that start before `settled` in text which
+    /// starts at `base` of the whole input, return the start of the logical
+    /// EOF if it occurs, the input after it should not be read.
+    pub(crate) fn advance(
+        &mut self,
+        text: &str,
+        base: usize,
+        settled: usize,
+    ) -> Option<usize> {
+        if self.eof.is_some() {
+            return self.eof;
+        }
+        let find = |marker: &Marker, from: usize| {
+            marker
+                .find_in(text, from - base)
+                .map(|range| base + range.start..base + range.end)
+                .filter(|range| range.start < settled)
+        };
+
+        let sof = match (self.sof, &self.markers.sof) {
+            (Some(sof), _) => sof,
+            (None, Some(marker)) => match find(marker, self.sof_from) {
+                Some(range) => *self.sof.insert(range.end),
+                None => {
+                    self.sof_from = settled;
+                    return None;
+                }
+            },
+            (None, None) => unreachable!("logical start is the input start"),
+        };
+
+        // the EOF is searched after the logical start.
+        let mut limit = settled;
+        if let Some(marker) = &self.markers.eof {
+            match find(marker, self.eof_from.max(sof)) {
+                Some(range) => {
+                    self.eof = Some(range.start);
+                    limit = range.start;
+                }
+                None => self.eof_from = settled,
+            }
+        }
+
+        for (pair, region) in self.markers.regions.iter().zip(&mut self.regions)
+        {
+            let (begin, end) = pair;
+            region.from = region.from.max(sof);
+            loop {
+                let found = match region.open {
+                    None => find(begin, region.from),
+                    Some(_) => find(end, region.from),
+                };
+                match (found, region.open) {
+                    (Some(range), _) if range.start >= limit => {
+                        region.from = limit;
+                        break;
+                    }
+                    (Some(opening), None) => {
+                        region.open = Some(opening.end);
+                        // an empty match would never move forward.
+                        region.from = opening.end.max(opening.start + 1);
+                    }
+                    (Some(closing), Some(inner_start)) => {
+                        self.kept.push(inner_start..closing.start);
+                        region.open = None;
+                        region.from = closing.end.max(region.from);
+                    }
+                    (None, _) => {
+                        region.from = limit;
+                        break;
+                    }
+                }
+            }
+        }
+        self.eof
+    }
+
+    /// Byte ranges that should be ignored in the input between `start`
+    /// and `end`, which has been settled by `advance`, the ranges are
+    /// relative to `start`.
+    pub(crate) fn ignored(
+        &mut self,
+        start: usize,
+        end: usize,
+    ) -> Vec<Range<usize>> {
+        let relative =
+            |range: Range<usize>| range.start - start..range.end - start;
+        // the whole text is ignored if the logical start hasn't occurred.
+        let sof = self.sof.unwrap_or(end).clamp(start, end);
+        let mut ignored = Vec::new();
+        if sof > start {
+            ignored.push(relative(start..sof));
+        }
+        if self.markers.regions.is_empty() {
+            return ignored;
+        }
+
+        // ignore the gaps between kept regions, a region that hasn't been
+        // closed lasts to the end of the text.
+        let mut regions: Vec<Range<usize>> = self
+            .kept
+            .iter()
+            .cloned()
+            .chain(self.regions.iter().filter_map(|r| r.open.map(|s| s..end)))
+            .collect();
+        regions.sort_by_key(|region| region.start);
+        let mut gap_start = sof;
+        for region in regions {
+            if region.start >= end {
+                break;
+            }
+            if region.start > gap_start {
+                ignored.push(relative(gap_start..region.start));
+            }
+            gap_start = gap_start.max(region.end);
+        }
+        if gap_start < end {
+            ignored.push(relative(gap_start..end));
+        }
+        self.kept.retain(|region| region.end > end);
+        ignored
+    }
+}
+
+#[cfg(test)]
+mod markers_tests {
+    use std::ops::Range;
+
+    use super::Markers;
+
+    /// Apply markers on the whole text at once,
+    /// return the logical EOF and ignored ranges.
+    fn apply(
+        markers: &Markers,
+        text: &str,
+    ) -> (Option<usize>, Vec<Range<usize>>) {
+        let mut scan = markers.scan();
+        let eof = scan.advance(text, 0, text.len());
+        (eof, scan.ignored(0, eof.unwrap_or(text.len())))
+    }
+
+    #[test]
+    fn ignore_text_before_logical_start() {
+        let markers = Markers::new(false).sof("begin").unwrap();
+        let (eof, ignored) = apply(&markers, "a // begin b c");
+        assert_eq!((eof, ignored.len(), ignored[0].clone()), (None, 1, 0..10));
+        let (_, ignored) = apply(&markers, "a // b c");
+        assert_eq!((ignored.len(), ignored[0].clone()), (1, 0..8));
+    }
+
+    #[test]
+    fn find_logical_eof_after_logical_start() {
+        let text = "END a START b END c";
+        let markers =
+            Markers::new(false).sof("START").unwrap().eof("END").unwrap();
+        let (eof, ignored) = apply(&markers, text);
+        assert_eq!(eof, Some(14));
+        assert_eq!((ignored.len(), ignored[0].clone()), (1, 0..11));
+    }
+
+    #[test]
+    fn only_keep_text_inside_regions() {
+        let text = "a <b> c <d> e <f";
+        let markers = Markers::new(false).region("<{}>").unwrap();
+        assert_eq!(apply(&markers, text), (None, vec![0..3, 4..9, 10..15]));
+    }
+
+    #[test]
+    fn regex_markers_work() {
+        let text = "x\n#region a\ny\n#endregion\nz";
+        let markers = Markers::new(true)
+            .region(r"(?m)^#region.*$\n{}(?m)^#endregion")
+            .unwrap();
+        assert_eq!(apply(&markers, text), (None, vec![0..12, 14..26]));
+        assert!(Markers::new(true).eof("(").is_err());
+    }
+
+    #[test]
+    fn apply_markers_piece_by_piece() {
+        let text = "a [b START c <d [e] f> g [h <i> END j [k";
+        let markers = || {
+            Markers::new(false)
+                .sof("START")
+                .unwrap()
+                .eof("END")
+                .unwrap()
+                .region("<{}>")
+                .unwrap()
+                .region("[{}]")
+                .unwrap()
+        };
+        let ignored_bytes = |ranges: Vec<Range<usize>>, start: usize| {
+            ranges.into_iter().flat_map(move |r| r.start + start..r.end + start)
+        };
+        let (eof, ignored) = apply(&markers(), text);
+        let expect: Vec<usize> = ignored_bytes(ignored, 0).collect();
+
+        // settle the text byte by byte, with the text before
+        // the settled part trimmed.
+        let markers = markers();
+        let mut scan = markers.scan();
+        let mut actual = Vec::new();
+        let mut start = 0usize;
+        for settled in 1..=text.len() {
+            let base = start.saturating_sub(2);
+            let piece_eof = scan.advance(&text[base..], base, settled);
+            let end = piece_eof.unwrap_or(settled);
+            actual.extend(ignored_bytes(scan.ignored(start, end), start));
+            start = end;
+            if piece_eof.is_some() {
+                break;
+            }
+        }
+        assert_eq!(start, eof.unwrap());
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn invalid_region_is_an_error() {
+        assert!(Markers::new(false).region("begin").is_err());
+        assert!(Markers::new(false).region("{}end").is_err());
+    }
+}
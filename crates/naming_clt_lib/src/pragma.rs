@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::ops::Range;
+
+use fancy_regex::Regex;
+
+lazy_static! {
+    // without lookarounds, it never exceeds the backtrack limit
+    // on a long text, the following character is checked instead.
+    static ref DIRECTIVE: Regex = Regex::new(
+        r"naming:(ignore-next-line|ignore-start|ignore-end|ignore-word)([\w-])?"
+    )
+    .unwrap();
+}
+
+/// Inline directives in a source, which are usually written in comments:
+/// * `naming:ignore-next-line` ignores the line after it;
+/// * `naming:ignore-start` and `naming:ignore-end` ignore the lines
+///   between them, including their own lines, an unclosed start lasts
+///   to the end of the source;
+/// * `naming:ignore-word foo_bar, fooBar` ignores the listed words
+///   in the whole source.
+///
+/// Directives themselves are ignored too, so their words are never captured.
+/// With the comment ranges from a lexer, only directives in comments count.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Pragmas {
+    pub(crate) ignored: Vec<Range<usize>>,
+    // the ignored ranges of `naming:ignore-start` blocks.
+    pub(crate) blocks: Vec<Range<usize>>,
+    pub(crate) words: HashSet<String>,
+}
+
+impl Pragmas {
+    /// `in_block` means the text starts inside an ignored block,
+    /// like the rest of a source that is captured piece by piece.
+    pub(crate) fn parse(
+        text: &str,
+        comments: Option<&[Range<usize>]>,
+        in_block: bool,
+    ) -> Pragmas {
+        let in_comment = |offset: usize| match comments {
+            Some(comments) => {
+                comments.iter().any(|comment| comment.contains(&offset))
+            }
+            None => true,
+        };
+        let mut pragmas = Pragmas::default();
+        let mut start_of_block = if in_block { Some(0) } else { None };
+        for cap in DIRECTIVE.captures_iter(text) {
+            let cap = cap.unwrap();
+            let whole = cap.get(0).unwrap();
+            if cap.get(2).is_some() || !in_comment(whole.start()) {
+                continue;
+            }
+            let line_end = line_end_of(text, whole.end());
+            match (cap.get(1).unwrap().as_str(), start_of_block) {
+                // directives inside a block are ignored with it.
+                ("ignore-end", Some(start)) => {
+                    pragmas.ignored.push(start..line_end);
+                    pragmas.blocks.push(start..line_end);
+                    start_of_block = None;
+                }
+                (_, Some(_)) => {}
+                ("ignore-start", None) => {
+                    start_of_block = Some(line_start_of(text, whole.start()))
+                }
+                ("ignore-next-line", None) => {
+                    let next_line_end = line_end_of(text, line_end + 1);
+                    pragmas.ignored.push(whole.start()..next_line_end);
+                }
+                ("ignore-word", None) => {
+                    pragmas.words.extend(
+                        text[whole.end()..line_end]
+                            .split(|c: char| c.is_whitespace() || c == ',')
+                            // skip comment closers like "*/" and "-->".
+                            .filter(|word| {
+                                word.chars().any(|c| c.is_alphanumeric())
+                            })
+                            .map(str::to_string),
+                    );
+                    pragmas.ignored.push(whole.start()..line_end);
+                }
+                // an unpaired end does nothing but hide itself.
+                _ => pragmas.ignored.push(whole.range()),
+            }
+        }
+        if let Some(start) = start_of_block {
+            pragmas.ignored.push(start..text.len());
+            pragmas.blocks.push(start..text.len());
+        }
+        pragmas
+    }
+}
+
+/// Byte offset of the first character of the line where `offset` is in.
+fn line_start_of(text: &str, offset: usize) -> usize {
+    text[..offset].rfind('\n').map_or(0, |i| i + 1)
+}
+
+/// Byte offset of the line break (or the end of text)
+/// of the line where `offset` is in.
+fn line_end_of(text: &str, offset: usize) -> usize {
+    if offset >= text.len() {
+        return text.len();
+    }
+    text[offset..].find('\n').map_or(text.len(), |i| offset + i)
+}
+
+#[cfg(test)]
+mod pragmas_tests {
+    use super::Pragmas;
+
+    #[test]
+    fn ignore_next_line() {
+        let text = "a\n// naming:ignore-next-line\nb\nc";
+        let pragmas = Pragmas::parse(text, None, false);
+        assert_eq!(pragmas.ignored, vec![5..30]);
+        assert!(pragmas.words.is_empty());
+    }
+
+    #[test]
+    fn ignore_lines_between_start_and_end() {
+        let text = "a # naming:ignore-start\nb # naming:ignore-next-line\n\
+            # naming:ignore-end\nc # naming:ignore-start\nd";
+        let pragmas = Pragmas::parse(text, None, false);
+        assert_eq!(pragmas.ignored, vec![0..71, 72..text.len()]);
+    }
+
+    #[test]
+    fn ignore_listed_words() {
+        let text = "/* naming:ignore-word foo_bar, fooBar */\nfoo_bar";
+        let pragmas = Pragmas::parse(text, None, false);
+        assert_eq!(pragmas.ignored, vec![3..40]);
+        let mut words: Vec<_> = pragmas.words.into_iter().collect();
+        words.sort();
+        assert_eq!(words, vec!["fooBar", "foo_bar"]);
+    }
+
+    #[test]
+    fn unknown_directives_are_not_pragmas() {
+        let text = "naming:ignore-words a\nnaming:ignore-line b";
+        assert_eq!(Pragmas::parse(text, None, false), Pragmas::default());
+    }
+
+    #[test]
+    fn only_directives_in_comments_count_with_comment_ranges() {
+        let text =
+            "s = \"naming:ignore-next-line\"\na\n// naming:ignore-word b";
+        let comment = 32..55;
+        let pragmas =
+            Pragmas::parse(text, Some(std::slice::from_ref(&comment)), false);
+        assert_eq!(pragmas.ignored, vec![35..55]);
+        assert!(pragmas.words.contains("b"));
+        assert_eq!(Pragmas::parse(text, Some(&[]), false), Pragmas::default());
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+use std::fs;
+
+use crate::extraction::{Capture, Position};
+use crate::language::{Language, CPP_KEYWORDS};
+
+/// Which built-in keyword lists are used, see `Stopwords::keywords`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Keywords {
+    Detected,
+    Of(Language),
+    All,
+    Off,
+}
+
+/// Answer user's `--keywords` and `--exclude-words` options,
+/// remove language keywords and user's stopwords from captures
+/// before they are filtered, so that only project identifiers are left.
+#[derive(Debug, Clone)]
+pub struct Stopwords {
+    keywords: Keywords,
+    words: HashSet<String>,
+}
+
+impl Default for Stopwords {
+    fn default() -> Self {
+        Stopwords { keywords: Keywords::Detected, words: HashSet::new() }
+    }
+}
+
+impl Stopwords {
+    /// Remove the keywords of the language detected from each file's
+    /// extension, sources without a detected language are kept as they are.
+    pub fn new() -> Stopwords {
+        Stopwords::default()
+    }
+
+    /// Choose built-in keyword lists by name:
+    /// * "auto": the language detected from each file's extension;
+    /// * "all": all languages' lists, on every source;
+    /// * "none": no list at all;
+    /// * a language name (see `Language::from_name`): that language's list,
+    ///   on every source.
+    pub fn keywords(mut self, name: &str) -> Result<Stopwords, String> {
+        self.keywords = match name {
+            "auto" => Keywords::Detected,
+            "all" => Keywords::All,
+            "none" => Keywords::Off,
+            name => Keywords::Of(Language::from_name(name)?),
+        };
+        Ok(self)
+    }
+
+    /// Remove given words from captures of all sources,
+    /// they are matched case-sensitively.
+    pub fn exclude_words<T: AsRef<str>>(mut self, words: &[T]) -> Stopwords {
+        self.words.extend(words.iter().map(|word| word.as_ref().to_string()));
+        self
+    }
+
+    /// Read the words to exclude from a file, words are separated
+    /// by whitespaces, and the rest of a line after "#" is a comment.
+    pub fn exclude_words_from(self, file: &str) -> Result<Stopwords, String> {
+        let text = fs::read_to_string(file)
+            .map_err(|msg| format!("naming: {}: {}", file, msg))?;
+        let words: Vec<&str> = text
+            .lines()
+            .flat_map(|line| {
+                let content = line.split('#').next().unwrap_or_default();
+                content.split_whitespace()
+            })
+            .collect();
+        Ok(self.exclude_words(&words))
+    }
+
+    /// Drop the captures of excluded words, and the occurrences
+    /// of keywords in sources of the languages they belong to,
+    /// captures whose occurrences are all dropped are dropped too.
+    pub fn remove_from(&self, captures: Vec<Capture>) -> Vec<Capture> {
+        captures
+            .into_iter()
+            .filter(|capture| !self.words.contains(&capture.word))
+            .filter_map(|Capture { word, positions, tags }| {
+                if positions.is_empty() {
+                    let is_keyword = self.is_keyword_in(&word, None);
+                    return if is_keyword {
+                        None
+                    } else {
+                        Some(Capture { word, positions, tags })
+                    };
+                }
+                let positions: Vec<Position> = positions
+                    .into_iter()
+                    .filter(|position| {
+                        !self.is_keyword_in(&word, position.path.as_deref())
+                    })
+                    .collect();
+                if positions.is_empty() {
+                    None
+                } else {
+                    Some(Capture { word, positions, tags })
+                }
+            })
+            .collect()
+    }
+
+    fn is_keyword_in(&self, word: &str, path: Option<&str>) -> bool {
+        let is_keyword_of =
+            |language: Language| language.is_keyword(word, path);
+        match self.keywords {
+            Keywords::Detected => {
+                path.and_then(Language::from_path).is_some_and(is_keyword_of)
+            }
+            Keywords::Of(language) => is_keyword_of(language),
+            Keywords::All => {
+                Language::ALL.iter().any(|l| is_keyword_of(*l))
+                    || CPP_KEYWORDS.contains(&word)
+            }
+            Keywords::Off => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod stopwords_tests {
+    use super::Stopwords;
+    use crate::extraction::{Captor, Source};
+    use crate::to_string_vec;
+
+    fn words_of(captures: Vec<crate::Capture>) -> Vec<String> {
+        captures.into_iter().map(|capture| capture.word).collect()
+    }
+
+    #[test]
+    fn remove_keywords_of_detected_languages() {
+        let sources = vec![
+            Source {
+                path: Some("a.rs".to_string()),
+                ..Source::from("let self_ref = self;".to_string())
+            },
+            Source::from("self".to_string()),
+        ];
+        let captures = Captor::new(None).unwrap().capture(sources);
+        let captures = Stopwords::new().remove_from(captures);
+        assert_eq!(words_of(captures.clone()), vec!["self_ref", "self"]);
+        // only the occurrence in stdin is kept.
+        assert_eq!(captures[1].positions.len(), 1);
+        assert_eq!(captures[1].positions[0].path, None);
+    }
+
+    #[test]
+    fn choose_keyword_lists_by_name() {
+        let captor = Captor::new(None).unwrap();
+        let captures =
+            || captor.capture(vec![Source::from("int x_y def".to_string())]);
+        let remove_with = |name: &str| {
+            words_of(
+                Stopwords::new()
+                    .keywords(name)
+                    .unwrap()
+                    .remove_from(captures()),
+            )
+        };
+        assert_eq!(remove_with("c"), vec!["x_y", "def"]);
+        assert_eq!(remove_with("all"), vec!["x_y"]);
+        assert_eq!(remove_with("none"), vec!["int", "x_y", "def"]);
+        assert!(Stopwords::new().keywords("cobol").is_err());
+    }
+
+    #[test]
+    fn remove_excluded_words() {
+        let captures = Captor::new(None)
+            .unwrap()
+            .capture(vec![Source::from("foo_bar fooBar baz".to_string())]);
+        let stopwords = Stopwords::new()
+            .exclude_words(&to_string_vec(vec!["fooBar", "baz"]));
+        assert_eq!(words_of(stopwords.remove_from(captures)), vec!["foo_bar"]);
+    }
+}
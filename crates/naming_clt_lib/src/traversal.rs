@@ -0,0 +1,232 @@
+use std::path::Path;
+
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::WalkBuilder;
+
+/// Directories that are skipped by default, they usually contain
+/// build outputs or dependencies rather than the user's own code.
+const SKIPPED_DIRS: [&str; 2] = ["target", "node_modules"];
+
+/// Answer user's `--include`, `--exclude`, `--follow-links`
+/// and `--keep-going` options,
+/// expand directories in given paths into the files inside them.
+#[derive(Debug, Clone, Default)]
+pub struct Walker {
+    includes: Vec<String>,
+    excludes: Vec<String>,
+    follow_links: bool,
+    keep_going: bool,
+    errors: Vec<String>,
+}
+
+impl Walker {
+    pub fn new() -> Walker {
+        Walker::default()
+    }
+
+    /// Only keep the files whose paths match one of given globs,
+    /// like "*.rs" or "src/**/*.java".
+    pub fn include(mut self, globs: Vec<String>) -> Walker {
+        self.includes = globs;
+        self
+    }
+
+    /// Skip the files and directories whose paths match one of given globs,
+    /// takes precedence over `include`.
+    pub fn exclude(mut self, globs: Vec<String>) -> Walker {
+        self.excludes = globs;
+        self
+    }
+
+    /// Follow symbolic links when walking directories,
+    /// they are skipped by default.
+    pub fn follow_links(mut self, follow: bool) -> Walker {
+        self.follow_links = follow;
+        self
+    }
+
+    /// Skip the entries that can't be read when walking directories,
+    /// instead of returning an Err on the first one of them,
+    /// errors are kept in `errors`.
+    pub fn keep_going(mut self, keep_going: bool) -> Walker {
+        self.keep_going = keep_going;
+        self
+    }
+
+    /// Messages of the entries skipped in keep going mode.
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Keep file paths as they are, and replace each directory path with
+    /// the files inside it (recursively, sorted by names), except:
+    /// * files and directories ignored by `.gitignore` and `.ignore` files;
+    /// * hidden files and directories, and `target`, `node_modules` directories;
+    /// * files that don't match include globs, or match exclude globs.
+    ///
+    /// If there is an invalid glob or an unreadable directory, return an Err.
+    pub fn files_of<T: AsRef<str>>(
+        &mut self,
+        paths: &[T],
+    ) -> Result<Vec<String>, String> {
+        let mut files = Vec::new();
+        for path in paths {
+            let path = path.as_ref();
+            if Path::new(path).is_dir() {
+                files.extend(self.walk(path)?);
+            } else {
+                // a file that user passes explicitly is never skipped,
+                // let reading it reports the error if it doesn't exist.
+                files.push(path.to_string());
+            }
+        }
+        Ok(files)
+    }
+
+    fn walk(&mut self, dir: &str) -> Result<Vec<String>, String> {
+        let walker = WalkBuilder::new(dir)
+            .overrides(self.build_overrides(dir)?)
+            .follow_links(self.follow_links)
+            .hidden(true)
+            .git_ignore(true)
+            .ignore(true)
+            // `.gitignore` works outside git repositories too.
+            .require_git(false)
+            .filter_entry(|entry| {
+                let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+                !(is_dir && entry.depth() > 0 && is_skipped_dir(entry.path()))
+            })
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+
+        let mut files = Vec::new();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(msg) => {
+                    let msg = format!("naming: {}: {}", dir, msg);
+                    if self.keep_going {
+                        self.errors.push(msg);
+                        continue;
+                    }
+                    return Err(msg);
+                }
+            };
+            if entry.file_type().is_some_and(|t| t.is_file()) {
+                files.push(entry.path().to_string_lossy().to_string());
+            }
+        }
+        Ok(files)
+    }
+
+    /// Globs are matched against paths relative to the walked directory.
+    fn build_overrides(&self, dir: &str) -> Result<Override, String> {
+        let mut builder = OverrideBuilder::new(dir);
+        let globs = self
+            .includes
+            .iter()
+            .cloned()
+            .chain(self.excludes.iter().map(|glob| format!("!{}", glob)));
+        for glob in globs {
+            builder
+                .add(&glob)
+                .map_err(|msg| format!("naming: glob `{}`: {}", glob, msg))?;
+        }
+        builder.build().map_err(|msg| format!("naming: {}", msg))
+    }
+}
+
+fn is_skipped_dir(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| SKIPPED_DIRS.contains(&name))
+}
+
+#[cfg(test)]
+mod walker_tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use super::Walker;
+    use crate::to_string_vec;
+
+    /// A directory tree under temp dir:
+    /// .gitignore (ignores "*.log"), a.rs, b.py, c.log, .hidden/d.rs,
+    /// target/e.rs, node_modules/f.js, sub/g.rs, sub/h.py
+    fn create_tree(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "naming_walker_tests_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        for file in &[
+            "a.rs",
+            "b.py",
+            "c.log",
+            ".hidden/d.rs",
+            "target/e.rs",
+            "node_modules/f.js",
+            "sub/g.rs",
+            "sub/h.py",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "x").unwrap();
+        }
+        fs::write(root.join(".gitignore"), "*.log\n").unwrap();
+        root
+    }
+
+    fn relative_files(mut walker: Walker, root: &PathBuf) -> Vec<String> {
+        walker
+            .files_of(&[root.to_str().unwrap()])
+            .unwrap()
+            .into_iter()
+            .map(|file| {
+                let path = PathBuf::from(file);
+                let path = path.strip_prefix(root).unwrap().to_str().unwrap();
+                path.replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn walk_directory_with_default_rules() {
+        let root = create_tree("default");
+        let actual = relative_files(Walker::new(), &root);
+        assert_eq!(
+            actual,
+            to_string_vec(vec!["a.rs", "b.py", "sub/g.rs", "sub/h.py"])
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn walk_directory_with_globs() {
+        let root = create_tree("globs");
+        let walker = Walker::new()
+            .include(to_string_vec(vec!["*.rs"]))
+            .exclude(to_string_vec(vec!["sub/**"]));
+        let actual = relative_files(walker, &root);
+        assert_eq!(actual, to_string_vec(vec!["a.rs"]));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keep_explicit_file_paths() {
+        let actual = Walker::new()
+            .include(to_string_vec(vec!["*.rs"]))
+            .files_of(&["not_exist.txt"])
+            .unwrap();
+        assert_eq!(actual, to_string_vec(vec!["not_exist.txt"]));
+    }
+
+    #[test]
+    fn invalid_glob_is_an_error() {
+        let root = create_tree("invalid");
+        let mut walker = Walker::new().include(to_string_vec(vec!["a[.rs"]));
+        assert!(walker.files_of(&[root.to_str().unwrap()]).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -43,3 +43,56 @@ fn apply_logical_eof_on_each_file_when_read_multiple_files() {
         lib::to_string_vec(vec!["front-a ", "front-b hold after-b"])
     );
 }
+
+#[test]
+fn skip_binary_files_with_warnings() {
+    let mut reader = lib::Reader::new(None);
+    let sources =
+        reader.read_files(&["tests/data/binary.bin", "tests/data/b.txt"]);
+    let texts: Vec<String> =
+        sources.unwrap().into_iter().map(|source| source.text).collect();
+    assert_eq!(texts, lib::to_string_vec(vec!["front-b hold after-b"]));
+    assert_eq!(
+        reader.warnings(),
+        ["naming: tests/data/binary.bin: binary file, skipped.".to_string()]
+    );
+}
+
+#[test]
+fn decode_utf_16_file_with_bom() {
+    let text = lib::read_from_files(&["tests/data/utf16le.txt"], None);
+    assert_eq!(text.unwrap(), lib::to_string_vec(vec!["pageSize"]));
+}
+
+#[test]
+fn decode_file_with_given_encoding() {
+    let file = ["tests/data/latin1.txt"];
+    assert!(lib::read_from_files(&file, None).is_err());
+
+    let mut reader = lib::Reader::new(None).encoding("latin1").unwrap();
+    let source = reader.read_files(&file).unwrap().pop().unwrap();
+    assert_eq!(source.text, "caf\u{e9}Menu = 1");
+
+    let mut reader = lib::Reader::new(None).lossy(true);
+    let source = reader.read_files(&file).unwrap().pop().unwrap();
+    assert_eq!(source.text, "caf\u{fffd}Menu = 1");
+}
+
+#[test]
+fn unknown_encoding_is_an_error() {
+    assert!(lib::Reader::new(None).encoding("no-such-encoding").is_err());
+}
+
+#[test]
+fn keep_going_when_some_files_fail() {
+    let files = ["tests/data/not_exist.txt", "tests/data/b.txt"];
+    assert!(lib::Reader::new(None).read_files(&files).is_err());
+
+    let mut reader = lib::Reader::new(None).keep_going(true);
+    let sources = reader.read_files(&files).unwrap();
+    assert_eq!(sources.len(), 1);
+    assert_eq!(reader.errors().len(), 1);
+    assert!(
+        reader.errors()[0].starts_with("naming: tests/data/not_exist.txt: ")
+    );
+}
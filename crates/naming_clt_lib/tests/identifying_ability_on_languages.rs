@@ -1,16 +1,13 @@
-// These tests show that it is impossible to
-// design a common set of extraction rules for different languages.
-// Even the extraction of the desired words
-// cannot be done appropriately in a single language.
-//
-// We learned about the limitations of this tool,
-// and marked this in the document for users.
+// These tests show that a common set of extraction rules
+// for different languages captures more than the desired words,
+// until a lexer that knows the language blanks out comments,
+// string literals and the names in statements like "package data;"
+// and "return a - b;", which refer to existing names only.
 
 use naming_lib::{which_case, NamingCase};
 
-use naming_clt_lib::{self as lib, Captor, Filter};
+use naming_clt_lib::{self as lib, Captor, Filter, Language, Lexer, Syntax};
 
-#[ignore]
 #[test]
 fn java() {
     let text = lib::read_from_files(&["tests/data/java.txt"], None).unwrap();
@@ -18,24 +15,24 @@ fn java() {
     // java variables are in camel case.
     let actual =
         Filter::new(Some(vec!["c".to_string()])).unwrap().to_naming_cases_from(
-            Captor::new(Some(lib::to_string_vec(vec![r"\s \s*=", r"\s \s*;"])))
-                .unwrap()
-                .capture_words(text),
+            Captor::new(Some(lib::to_string_vec(vec![
+                r"\s{}\s*=",
+                r"\s{}\s*;",
+            ])))
+            .unwrap()
+            .lexer(Lexer::new(Some(Syntax::CLike)).language(Language::Java))
+            .capture_words(text),
         );
 
-    // =========================Failure:
-    // line 1: "package data;"
-    // There is no language-insensitive general rule
-    // to identifying difference between lines like this
-    // with common assignment statements like
-    // line 51: "private String name;"
+    // line 1: "package data;" looks like
+    // line 51: "private String name;" to the locators,
+    // the lexer tells them apart by the "package" keyword.
     let expect = to_naming_case_vec(&[
         "count",
         "targetIsLive",
         "liveNeighborCount",
         "rowSize",
         "colSize",
-        "data",
         "name",
         "category",
     ]);
@@ -43,11 +40,42 @@ fn java() {
     assert_eq!(actual, expect);
 }
 
+// With the built-in preset, Java's declarations, parameters and fields
+// are located by their grammar rather than by a pair of spaces.
+#[test]
+fn java_with_language_preset() {
+    let text = lib::read_from_files(&["tests/data/java.txt"], None).unwrap();
+
+    let actual =
+        Filter::new(Some(vec!["c".to_string()])).unwrap().to_naming_cases_from(
+            Captor::from_language(Language::Java).capture_words(text),
+        );
+
+    // class names "Example" and "Entity" are in pascal case.
+    let expect = to_naming_case_vec(&[
+        "checkCellNextState",
+        "judgeLiveCell",
+        "judgeDeadCell",
+        "countLiveCells",
+        "grid",
+        "i",
+        "j",
+        "count",
+        "targetIsLive",
+        "liveNeighborCount",
+        "rowSize",
+        "name",
+        "category",
+        "colSize",
+    ]);
+
+    assert_eq!(actual, expect);
+}
+
 fn to_naming_case_vec(array: &[&str]) -> Vec<NamingCase> {
-    array.iter().map(|id| which_case(*id)).collect()
+    array.iter().map(|id| which_case(id)).collect()
 }
 
-#[ignore]
 #[test]
 fn javascript() {
     let text =
@@ -56,25 +84,65 @@ fn javascript() {
     // variables in example file are in camel case.
     let actual =
         Filter::new(Some(vec!["c".to_string()])).unwrap().to_naming_cases_from(
-            Captor::new(Some(lib::to_string_vec(vec![r"\s \s*=", r"\s \s*;"])))
-                .unwrap()
-                .capture_words(text),
+            Captor::new(Some(lib::to_string_vec(vec![
+                r"\s{}\s*=",
+                r"\s{}\s*;",
+            ])))
+            .unwrap()
+            .lexer(Lexer::new(Some(Syntax::JsLike)))
+            .capture_words(text),
         );
 
-    // =========================Failure:
     // line 9: "for (let i = 0; i < timePoints.length - 1; i++) {" -> "i"
-    // can't fix it.
-    // line 16: "return a - b;" -> "b"
-    // should I add logic that discard words that matched in method params?
-    // no, that's unnecessary complex.
+    // is a loop variable, while "b" in line 16: "return a - b;"
+    // is only referred, the lexer skips the "return" statement.
     let expect = to_naming_case_vec(&[
         "findMinDifference",
         "sorted",
         "headTail",
         "min",
         "i",
-        "b",
     ]);
 
     assert_eq!(actual, expect);
 }
+
+#[test]
+fn lexer_skips_words_in_comments() {
+    let text =
+        lib::read_from_files(&["tests/data/javascript.txt"], None).unwrap();
+    let lexer = Lexer::new(Some(Syntax::JsLike));
+
+    let actual = Captor::from_lexer(lexer).capture_words(text.clone());
+    // "@param {string[]} timePoints" etc. in doc comments are skipped.
+    for word in ["param", "string", "number", "return", "returns", "String"] {
+        assert!(!actual.contains(&word.to_string()), "{}", word);
+    }
+    assert!(actual.contains(&"timeStr".to_string()));
+
+    let actual =
+        Captor::from_lexer(lexer.include_comments(true)).capture_words(text);
+    assert!(actual.contains(&"param".to_string()));
+}
+
+#[test]
+fn lexer_skips_words_in_strings_and_comments() {
+    let text = lib::to_string_vec(vec![
+        r#"String greeting = " name = value;"; // count = 1;"#,
+    ]);
+    let captor = || {
+        Captor::new(Some(lib::to_string_vec(vec![r"\s{}\s*=", r"\s{}\s*;"])))
+            .unwrap()
+    };
+
+    let actual = captor()
+        .lexer(Lexer::new(Some(Syntax::CLike)))
+        .capture_words(text.clone());
+    assert_eq!(actual, lib::to_string_vec(vec!["greeting"]));
+
+    let actual = captor().capture_words(text);
+    assert_eq!(
+        actual,
+        lib::to_string_vec(vec!["greeting", "name", "count", "value"])
+    );
+}
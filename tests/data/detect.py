@@ -0,0 +1 @@
+self.size = len(items)
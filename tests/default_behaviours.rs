@@ -47,3 +47,14 @@ camelCase CAMEL_CASE camel_case camel-case camelCase CamelCase
 PascalCase PASCAL_CASE pascal_case pascal-case pascalCase PascalCase",
         );
 }
+
+#[test]
+fn read_unicode_identifiers() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.write_stdin("größe ÉtatCourant 变量_名").assert().success().stdout(
+        "\
+größe GRÖSSE größe größe größe Größe
+ÉtatCourant ÉTAT_COURANT état_courant état-courant étatCourant ÉtatCourant
+变量_名 变量_名 变量_名 变量-名 变量名 变量名",
+    );
+}
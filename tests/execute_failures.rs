@@ -18,9 +18,12 @@ fn input_not_exist_file() {
 }
 
 #[test]
-fn filter_option_has_hungarian_camel_conflict() {
+fn filter_option_excludes_all_formats() {
     let mut cmd = Command::cargo_bin("naming").unwrap();
-    cmd.arg("-f=c,h").assert().failure();
+    cmd.arg("--exclude-format=S,s,k,c,p,T,K,d,r,f,F,A,t,e")
+        .write_stdin("a")
+        .assert()
+        .failure();
 
     let err_msg = cmd.output().unwrap().stderr;
     let err_msg = String::from_utf8_lossy(&err_msg);
@@ -38,3 +41,32 @@ fn wrong_locator_that_can_not_be_converted_to_regex() {
     let err_msg = String::from_utf8_lossy(&err_msg);
     assert!(it::contains("locator").eval(&err_msg));
 }
+
+#[test]
+fn keep_going_when_input_not_exist_file() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--keep-going")
+        .arg("--output=s")
+        .arg("not_exist.txt")
+        .arg("tests/data/one.txt")
+        .assert()
+        .code(2)
+        .stdout("userId user_id")
+        .stderr(it::contains("not_exist.txt:"));
+}
+
+#[test]
+fn keep_going_reports_errors_in_json() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--keep-going")
+        .arg("--json")
+        .arg("--output=s")
+        .arg("not_exist.txt")
+        .arg("tests/data/one.txt")
+        .assert()
+        .code(2)
+        .stdout(it::starts_with(
+            r#"{"result":[{"origin":"userId","snake":"user_id"}],"errors":["naming: not_exist.txt: "#,
+        ))
+        .stderr("");
+}
@@ -18,10 +18,182 @@ camelCase CAMEL_CASE camel_case camel-case camelCase CamelCase",
 fn filter_hungarian_option() {
     let mut cmd = Command::cargo_bin("naming").unwrap();
     cmd.arg("--filter=h")
-        .arg("tests/data/all.txt")
+        .write_stdin("strPageSize userName m_count")
+        .assert()
+        .success()
+        .stdout(
+            "\
+strPageSize PAGE_SIZE page_size page-size pageSize PageSize
+m_count COUNT count count count Count",
+        );
+}
+
+#[test]
+fn filter_hungarian_with_camel_and_prefixes() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--filter=c,h")
+        .arg("--hungarian-prefixes=n")
+        .arg("--json")
+        .arg("--output=s")
+        .write_stdin("nCount userName")
+        .assert()
+        .success()
+        .stdout(concat!(
+            r#"{"result":[{"origin":"nCount","snake":"count","prefix":"n"},"#,
+            r#"{"origin":"userName","snake":"user_name"}]}"#
+        ));
+}
+
+#[test]
+fn negated_filter_and_exclude_format() {
+    for args in [vec!["--filter=!k,!S,!s,!p"], vec!["--exclude-format=k,S,s,p"]]
+    {
+        let mut cmd = Command::cargo_bin("naming").unwrap();
+        cmd.args(args)
+            .arg("--output=s")
+            .write_stdin("page_size pageSize")
+            .assert()
+            .success()
+            .stdout("pageSize page_size");
+    }
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--filter=!c,h")
+        .arg("--output=s")
+        .write_stdin("page_size iPageSize")
+        .assert()
+        .success()
+        .stdout("page_size page_size\niPageSize page_size");
+}
+
+#[test]
+fn additional_cases() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--filter=T,d")
+        .arg("--word-pattern=dotted")
+        .arg("--output=A,r,K")
+        .write_stdin("Content-Type log.level page_size")
+        .assert()
+        .success()
+        .stdout("Content-Type Content_Type content/type CONTENT-TYPE\nlog.level Log_Level log/level LOG-LEVEL");
+}
+
+#[test]
+fn phrases_round_trip() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--filter=t,e")
+        .arg("--word-pattern=phrase")
+        .arg("--locator='{}'")
+        .arg("--output=s,t")
+        .arg("--json")
+        .write_stdin("label='Page size' heading='Page Size' id='page_size'")
         .assert()
         .success()
-        .stdout("Case CASE case case case Case");
+        .stdout(concat!(
+            r#"{"result":[{"origin":"Page size","snake":"page_size","title":"Page Size"},"#,
+            r#"{"origin":"Page Size","snake":"page_size","title":"Page Size"}]}"#
+        ));
+}
+
+#[test]
+fn phrases_in_normal_output() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--word-pattern=phrase")
+        .arg("--locator='{}'")
+        .arg("--output=s,t")
+        .write_stdin("label='Page size'")
+        .assert()
+        .success()
+        .stdout(r#""Page size" page_size "Page Size""#);
+}
+
+#[test]
+fn acronyms() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--acronyms=ID,xml")
+        .arg("--output=s,c,p")
+        .write_stdin("HTTPServer userId xml_http_request")
+        .assert()
+        .success()
+        .stdout(
+            "HTTPServer http_server httpServer HttpServer\n\
+            userId user_id userID UserID\n\
+            xml_http_request xml_http_request xmlHttpRequest XMLHttpRequest",
+        );
+}
+
+#[test]
+fn digit_policies() {
+    let cases = [
+        (
+            "attach-previous",
+            "v2Api utf8Decode",
+            "v2Api v2_api v2Api\nutf8Decode utf8_decode utf8Decode",
+        ),
+        (
+            "separate",
+            "v2Api utf_8_decode",
+            "v2Api v_2_api v2Api\nutf_8_decode utf_8_decode utf8Decode",
+        ),
+        (
+            "attach-next",
+            "v2Api v_2api",
+            "v2Api v_2api v2Api\nv_2api v_2api v2Api",
+        ),
+    ];
+    for (digits, input, output) in cases.iter() {
+        let mut cmd = Command::cargo_bin("naming").unwrap();
+        cmd.arg(format!("--digits={}", digits))
+            .arg("--output=s,c")
+            .write_stdin(*input)
+            .assert()
+            .success()
+            .stdout(*output);
+    }
+}
+
+#[test]
+fn affixes() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--output=s,c")
+        .write_stdin("__init__ _privateName name_")
+        .assert()
+        .success()
+        .stdout(
+            "__init__ __init__ __init__\n\
+            _privateName _private_name _privateName\n\
+            name_ name_ name_",
+        );
+
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--output=S")
+        .arg("--word-pattern=sigil")
+        .arg(r"--locator=(?<![\w$@]){}(?![\w?!])")
+        .arg("--strip-affixes")
+        .arg("--json")
+        .write_stdin("$scope")
+        .assert()
+        .success()
+        .stdout(concat!(
+            r#"{"result":[{"origin":"$scope","screaming_snake":"SCOPE","#,
+            r#""affix":{"leading":"$","trailing":""}}]}"#
+        ));
+
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--output=s")
+        .arg("--word-pattern=sigil")
+        .arg(r"--locator=(?<![\w$@]){}(?![\w?!])")
+        .write_stdin("isEmpty? saveAll!")
+        .assert()
+        .success()
+        .stdout("isEmpty? is_empty?\nsaveAll! save_all!");
+
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--filter=h")
+        .arg("--output=s")
+        .write_stdin("_strName")
+        .assert()
+        .success()
+        .stdout("_strName _name");
 }
 
 #[test]
@@ -49,6 +221,347 @@ fn locator() {
         .stdout("el EL el el el El\nbab BAB bab bab bab Bab");
 }
 
+#[test]
+fn lang() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--lang=python")
+        .write_stdin("def load(path: str):\n    content = path\n")
+        .assert()
+        .success()
+        .stdout(
+            "\
+load LOAD load load load Load
+content CONTENT content content content Content
+path PATH path path path Path",
+        );
+}
+
+#[test]
+fn lang_auto() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--output=s")
+        .arg("tests/data/detect.py")
+        .assert()
+        .success()
+        .stdout("self self\nsize size\nlen len\nitems items");
+
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--lang=auto")
+        .arg("--output=s")
+        .arg("tests/data/detect.py")
+        .assert()
+        .success()
+        .stdout("size size");
+}
+
+#[test]
+fn lang_conflicts_with_locator() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--lang=rust")
+        .arg(r"--locator=\s{}\s")
+        .arg("tests/data/one.txt")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn lexer() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--lexer=python")
+        .write_stdin("page_size = 10  # default page size\nname = 'a b'\n")
+        .assert()
+        .success()
+        .stdout(
+            "\
+page_size PAGE_SIZE page_size page-size pageSize PageSize
+name NAME name name name Name",
+        );
+}
+
+#[test]
+fn lexer_include_comments() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--lexer=c")
+        .arg("--include-comments")
+        .write_stdin("int a; // b")
+        .assert()
+        .success()
+        .stdout("a A a a a A\nb B b b b B");
+}
+
+#[test]
+fn positions() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--positions")
+        .arg("--output=s")
+        .arg("tests/data/one.txt")
+        .arg("tests/data/one.txt")
+        .assert()
+        .success()
+        .stdout(
+            "\
+tests/data/one.txt:1:1:userId user_id
+tests/data/one.txt:1:1:userId user_id",
+        );
+}
+
+#[test]
+fn positions_in_json() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--positions")
+        .arg("--json")
+        .arg("--output=s")
+        .write_stdin("a b\n a")
+        .assert()
+        .success()
+        .stdout(concat!(
+            r#"{"result":[{"origin":"a","snake":"a","positions":["#,
+            r#"{"path":null,"line":1,"column":1,"offset":0},"#,
+            r#"{"path":null,"line":2,"column":2,"offset":5}]},"#,
+            r#"{"origin":"b","snake":"b","positions":["#,
+            r#"{"path":null,"line":1,"column":3,"offset":2}]}]}"#
+        ));
+}
+
+#[test]
+fn keep_order() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg(r"--locator=#{}#,@{}@")
+        .arg("--keep-order")
+        .arg("--output=s")
+        .write_stdin("@first@ #second# @third@")
+        .assert()
+        .success()
+        .stdout("first first\nsecond second\nthird third");
+}
+
+#[test]
+fn locator_with_named_placeholders() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg(r"--locator={name}:\s*{type}")
+        .arg("--output=s")
+        .arg("--json")
+        .write_stdin("pageSize: PageSize")
+        .assert()
+        .success()
+        .stdout(concat!(
+            r#"{"result":[{"origin":"pageSize","snake":"page_size","tags":["name"]},"#,
+            r#"{"origin":"PageSize","snake":"page_size","tags":["type"]}]}"#
+        ));
+}
+
+#[test]
+fn dedup_by_words() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--dedup=words")
+        .arg("--output=S")
+        .write_stdin("pageSize PAGE_SIZE page-size maxSize")
+        .assert()
+        .success()
+        .stdout("pageSize,PAGE_SIZE,page-size PAGE_SIZE\nmaxSize MAX_SIZE");
+}
+
+#[test]
+fn stats_sorted_by_frequency() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--stats")
+        .arg("--sort-by-frequency")
+        .arg("--output=s")
+        .write_stdin("pageSize maxSize pageSize")
+        .assert()
+        .success()
+        .stdout("2 1 pageSize page_size\n1 1 maxSize max_size");
+}
+
+#[test]
+fn word_pattern() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--word-pattern=ident")
+        .arg("--output=S")
+        .write_stdin("page-size 42")
+        .assert()
+        .success()
+        .stdout("page PAGE\nsize SIZE");
+}
+
+#[test]
+fn word_pattern_with_capture_group() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--word-pattern=([a-z]+)").write_stdin("page").assert().failure();
+}
+
+#[test]
+fn walk_directory_with_include_and_exclude() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--include=*.txt")
+        .arg("--exclude=all.txt")
+        .arg("--output=s")
+        .arg("tests/data/walk")
+        .assert()
+        .success()
+        .stdout("page_count page_count\nuserId user_id");
+}
+
+#[test]
+fn sof_and_region() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--sof=START")
+        .arg("--region=<{}>")
+        .arg("--output=s")
+        .write_stdin("<pageSize> START <maxSize> minSize")
+        .assert()
+        .success()
+        .stdout("maxSize max_size");
+}
+
+#[test]
+fn inline_pragmas() {
+    let input = "# naming:ignore-word page_size\npage_size max_size\n\
+        # naming:ignore-start\nmin_size\n# naming:ignore-end\nfoo_bar";
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--output=c")
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout("max_size maxSize\nfoo_bar fooBar");
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--no-pragmas")
+        .arg("--output=c")
+        .write_stdin(input)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("min_size minSize"));
+}
+
+#[test]
+fn regex_markers() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--regex-markers")
+        .arg(r"--region=#\s*begin{}#\s*end")
+        .arg(r"--eof=\bSTOP\b")
+        .arg("--output=s")
+        .write_stdin("a # begin pageSize #end b #begin maxSize STOP minSize")
+        .assert()
+        .success()
+        .stdout("pageSize page_size\nmaxSize max_size");
+}
+
+#[test]
+fn keywords_and_exclude_words() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--keywords=java")
+        .arg("--exclude-words=tests/data/stopwords.lst")
+        .arg("--output=s")
+        .write_stdin("String pageSize = max_size; int min_size;")
+        .assert()
+        .success()
+        .stdout("min_size min_size");
+}
+
+#[test]
+fn keywords_default_to_lang() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--output=s")
+        .write_stdin("int page_size;")
+        .assert()
+        .success()
+        .stdout("int int\npage_size page_size");
+
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--lang=c")
+        .arg("--output=s")
+        .write_stdin("int page_size;")
+        .assert()
+        .success()
+        .stdout("page_size page_size");
+}
+
+#[test]
+fn keywords_none() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--lang=python")
+        .arg("--keywords=none")
+        .arg("--output=s")
+        .write_stdin("self = None")
+        .assert()
+        .success()
+        .stdout("self self");
+}
+
+#[test]
+fn keywords_kept_with_locator() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg(r"--locator=\b{}\.")
+        .arg("--output=s")
+        .arg("tests/data/detect.py")
+        .assert()
+        .success()
+        .stdout("self self");
+
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg(r"--locator=\b{}\.")
+        .arg("--keywords=auto")
+        .arg("--output=s")
+        .arg("tests/data/detect.py")
+        .assert()
+        .success()
+        .stdout("");
+}
+
+#[test]
+fn match_and_exclude_match() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--match=Id$")
+        .arg("--match=^order")
+        .arg("--exclude-match=^test")
+        .arg("--output=s")
+        .write_stdin("userId testUserId orderCount pageSize")
+        .assert()
+        .success()
+        .stdout("userId user_id\norderCount order_count");
+}
+
+#[test]
+fn length_and_word_count_bounds() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--min-length=2")
+        .arg("--max-words=2")
+        .arg("--skip-numbers")
+        .arg("--word-pattern=\\w+")
+        .arg("--output=s")
+        .write_stdin("i size pageSize max_page_size 0xFF 42")
+        .assert()
+        .success()
+        .stdout("size size\npageSize page_size");
+}
+
+#[test]
+fn invalid_count_is_an_error() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--min-length=-1").write_stdin("a").assert().failure();
+}
+
+#[test]
+fn encoding() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.arg("--encoding=latin1")
+        .arg("--output=s")
+        .write_stdin(&b"caf\xe9Menu"[..])
+        .assert()
+        .success()
+        .stdout("caf\u{e9}Menu caf\u{e9}_menu");
+}
+
+#[test]
+fn skip_binary_stdin_with_warning() {
+    let mut cmd = Command::cargo_bin("naming").unwrap();
+    cmd.write_stdin(&b"pageSize\x00"[..])
+        .assert()
+        .success()
+        .stdout("")
+        .stderr("naming: stdin: binary file, skipped.\n");
+}
+
 #[test]
 fn locator_support_lookaround() {
     let mut cmd = Command::cargo_bin("naming").unwrap();